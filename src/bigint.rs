@@ -0,0 +1,284 @@
+// an unsigned integer as big as it needs to be, enough for fibonacci numbers with thousands of
+// digits without a crate
+//
+// the digits are kept in base 1_000_000_000, lowest "digit" (limb) first, so printing is just each
+// limb padded to 9 decimal digits. no trailing zero limbs, zero is no limbs at all
+
+use std::{
+    cmp::Ordering,
+    fmt,
+    ops::{Add, AddAssign, Mul, Sub},
+    str::FromStr,
+};
+
+const BASE: u32 = 1_000_000_000;
+const BASE_DIGITS: usize = 9;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct BigUint {
+    limbs: Vec<u32>,
+}
+
+impl BigUint {
+    pub fn zero() -> Self {
+        BigUint::default()
+    }
+
+    pub fn one() -> Self {
+        BigUint::from(1u32)
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.limbs.is_empty()
+    }
+
+    // how many decimal digits it has, zero has one
+    pub fn digits(&self) -> usize {
+        match self.limbs.last() {
+            None => 1,
+            Some(top) => (self.limbs.len() - 1) * BASE_DIGITS + top.to_string().len(),
+        }
+    }
+
+    // None when other is bigger, like u32::checked_sub
+    pub fn checked_sub(&self, other: &BigUint) -> Option<BigUint> {
+        if *self < *other {
+            return None;
+        }
+        let mut limbs = Vec::with_capacity(self.limbs.len());
+        let mut borrow = 0;
+        for (i, limb) in self.limbs.iter().enumerate() {
+            let take = other.limbs.get(i).copied().unwrap_or(0) + borrow;
+            if *limb >= take {
+                limbs.push(limb - take);
+                borrow = 0;
+            } else {
+                limbs.push(limb + BASE - take);
+                borrow = 1;
+            }
+        }
+        let mut difference = BigUint { limbs };
+        difference.trim();
+        Some(difference)
+    }
+
+    // the remainder after dividing by m, which is all the pisano period needs
+    pub fn rem_u64(&self, m: u64) -> u64 {
+        assert!(
+            m != 0,
+            "attempt to calculate the remainder with a divisor of zero"
+        );
+        let remainder = self.limbs.iter().rev().fold(0u128, |remainder, limb| {
+            (remainder * u128::from(BASE) + u128::from(*limb)) % u128::from(m)
+        });
+        remainder as u64
+    }
+
+    // the binary digits, highest first (none for zero), for walking the bits of a huge n
+    pub fn bits(&self) -> Vec<bool> {
+        const CHUNK: u32 = 30;
+        let mut limbs = self.limbs.clone();
+        let mut bits = Vec::new();
+        while !limbs.is_empty() {
+            // divide by 2^30 from the top limb down, what's left over is the next 30 bits
+            let mut remainder = 0u64;
+            for limb in limbs.iter_mut().rev() {
+                let value = remainder * u64::from(BASE) + u64::from(*limb);
+                *limb = (value >> CHUNK) as u32;
+                remainder = value & ((1 << CHUNK) - 1);
+            }
+            bits.extend((0..CHUNK).map(|bit| remainder >> bit & 1 == 1));
+            while limbs.last() == Some(&0) {
+                limbs.pop();
+            }
+        }
+        // the last chunk is padded with zeros
+        while bits.last() == Some(&false) {
+            bits.pop();
+        }
+        bits.reverse();
+        bits
+    }
+
+    fn trim(&mut self) {
+        while self.limbs.last() == Some(&0) {
+            self.limbs.pop();
+        }
+    }
+}
+
+impl From<u32> for BigUint {
+    fn from(n: u32) -> Self {
+        BigUint::from(u128::from(n))
+    }
+}
+
+impl From<u64> for BigUint {
+    fn from(n: u64) -> Self {
+        BigUint::from(u128::from(n))
+    }
+}
+
+impl From<u128> for BigUint {
+    fn from(mut n: u128) -> Self {
+        let mut limbs = Vec::new();
+        while n > 0 {
+            limbs.push((n % u128::from(BASE)) as u32);
+            n /= u128::from(BASE);
+        }
+        BigUint { limbs }
+    }
+}
+
+// errors when it doesn't fit
+impl TryFrom<&BigUint> for u128 {
+    type Error = ();
+    fn try_from(n: &BigUint) -> Result<Self, Self::Error> {
+        n.limbs.iter().rev().try_fold(0u128, |total, limb| {
+            total
+                .checked_mul(u128::from(BASE))
+                .and_then(|total| total.checked_add(u128::from(*limb)))
+                .ok_or(())
+        })
+    }
+}
+
+impl AddAssign<&BigUint> for BigUint {
+    fn add_assign(&mut self, other: &BigUint) {
+        if self.limbs.len() < other.limbs.len() {
+            self.limbs.resize(other.limbs.len(), 0);
+        }
+        let mut carry = 0;
+        for (i, limb) in self.limbs.iter_mut().enumerate() {
+            let sum = *limb + other.limbs.get(i).copied().unwrap_or(0) + carry;
+            // two limbs and a carry are under 2 * BASE, which fits in a u32
+            *limb = sum % BASE;
+            carry = sum / BASE;
+            if carry == 0 && i >= other.limbs.len() {
+                break;
+            }
+        }
+        if carry > 0 {
+            self.limbs.push(carry);
+        }
+    }
+}
+
+impl Add<&BigUint> for &BigUint {
+    type Output = BigUint;
+    fn add(self, other: &BigUint) -> BigUint {
+        let mut sum = self.clone();
+        sum += other;
+        sum
+    }
+}
+
+impl Add for BigUint {
+    type Output = BigUint;
+    fn add(mut self, other: BigUint) -> BigUint {
+        self += &other;
+        self
+    }
+}
+
+// panics when other is bigger, like subtracting u32s in a debug build
+impl Sub<&BigUint> for &BigUint {
+    type Output = BigUint;
+    fn sub(self, other: &BigUint) -> BigUint {
+        self.checked_sub(other)
+            .expect("attempt to subtract with overflow")
+    }
+}
+
+// long multiplication, fine for numbers with tens of thousands of digits
+impl Mul<&BigUint> for &BigUint {
+    type Output = BigUint;
+    fn mul(self, other: &BigUint) -> BigUint {
+        if self.is_zero() || other.is_zero() {
+            return BigUint::zero();
+        }
+        let mut limbs = vec![0u32; self.limbs.len() + other.limbs.len()];
+        for (i, a) in self.limbs.iter().enumerate() {
+            let mut carry = 0u64;
+            for (j, b) in other.limbs.iter().enumerate() {
+                // under BASE + (BASE - 1)^2 + 2 * BASE, which fits in a u64
+                let product = u64::from(limbs[i + j]) + u64::from(*a) * u64::from(*b) + carry;
+                limbs[i + j] = (product % u64::from(BASE)) as u32;
+                carry = product / u64::from(BASE);
+            }
+            let mut k = i + other.limbs.len();
+            while carry > 0 {
+                let sum = u64::from(limbs[k]) + carry;
+                limbs[k] = (sum % u64::from(BASE)) as u32;
+                carry = sum / u64::from(BASE);
+                k += 1;
+            }
+        }
+        let mut product = BigUint { limbs };
+        product.trim();
+        product
+    }
+}
+
+impl Ord for BigUint {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // no trailing zero limbs, so more limbs is bigger
+        self.limbs
+            .len()
+            .cmp(&other.limbs.len())
+            .then_with(|| self.limbs.iter().rev().cmp(other.limbs.iter().rev()))
+    }
+}
+
+impl PartialOrd for BigUint {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+// {:>40} and friends work like they do for the built in integers
+impl fmt::Display for BigUint {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut text = match self.limbs.last() {
+            None => String::from("0"),
+            Some(top) => top.to_string(),
+        };
+        for limb in self.limbs.iter().rev().skip(1) {
+            text.push_str(&format!("{limb:09}"));
+        }
+        f.pad_integral(true, "", &text)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseBigUintError(String);
+
+impl fmt::Display for ParseBigUintError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "\"{}\" isn't a whole number", self.0)
+    }
+}
+
+impl std::error::Error for ParseBigUintError {}
+
+// decimal digits, underscores allowed between them like in rust source
+impl FromStr for BigUint {
+    type Err = ParseBigUintError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let digits: Vec<u8> = s.bytes().filter(|b| *b != b'_').collect();
+        if digits.is_empty() || !digits.iter().all(u8::is_ascii_digit) || s.starts_with('_') {
+            return Err(ParseBigUintError(s.to_string()));
+        }
+        let limbs = digits
+            .rchunks(BASE_DIGITS)
+            .map(|chunk| {
+                chunk
+                    .iter()
+                    .fold(0, |limb, digit| limb * 10 + u32::from(digit - b'0'))
+            })
+            .collect();
+        let mut n = BigUint { limbs };
+        n.trim();
+        Ok(n)
+    }
+}
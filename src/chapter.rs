@@ -0,0 +1,68 @@
+// every chapter module describes itself through the Chapter trait so the runner (and tests) can
+// enumerate them instead of relying on a hand maintained list of function calls
+
+use crate::output::Report;
+use crate::{
+    chapter_eight, chapter_eight_hw, chapter_five, chapter_four, chapter_nine, chapter_one,
+    chapter_seven, chapter_six, chapter_three, chapter_three_hw, chapter_two,
+};
+
+// a named part of a chapter that can be run on its own, e.g. `references` in chapter four
+pub struct Section {
+    pub name: &'static str,
+    pub run: fn(&mut dyn Report),
+}
+
+// Sync so the chapters can live in a static registry
+pub trait Chapter: Sync {
+    // chapter number in the book
+    fn number(&self) -> u8;
+    fn title(&self) -> &'static str;
+    // one line description shown by `list`
+    fn summary(&self) -> &'static str;
+    // the sub sections in the order the chapter runs them, empty if the chapter is one piece
+    fn sections(&self) -> &'static [Section] {
+        &[]
+    }
+    // runs the whole chapter, writing its output to out
+    fn run(&self, out: &mut dyn Report);
+
+    // homework modules share a number with the chapter they belong to
+    fn is_homework(&self) -> bool {
+        false
+    }
+
+    // id used on the command line, "4" or "3-hw"
+    fn id(&self) -> String {
+        if self.is_homework() {
+            format!("{}-hw", self.number())
+        } else {
+            self.number().to_string()
+        }
+    }
+
+    fn section(&self, name: &str) -> Option<&'static Section> {
+        self.sections().iter().find(|section| section.name == name)
+    }
+}
+
+// all chapters in the order they appear in the book (homework straight after its chapter)
+pub static CHAPTERS: &[&dyn Chapter] = &[
+    &chapter_one::ChapterOne,
+    &chapter_two::ChapterTwo,
+    &chapter_three::ChapterThree,
+    &chapter_three_hw::ChapterThreeHw,
+    &chapter_four::ChapterFour,
+    &chapter_five::ChapterFive,
+    &chapter_six::ChapterSix,
+    &chapter_seven::ChapterSeven,
+    &chapter_eight::ChapterEight,
+    &chapter_eight_hw::ChapterEightHw,
+    &chapter_nine::ChapterNine,
+];
+
+// looks a chapter up by id, also accepts "chapter_4" and "4-HW"
+pub fn find(id: &str) -> Option<&'static dyn Chapter> {
+    let id = id.trim_start_matches("chapter_").to_lowercase();
+    CHAPTERS.iter().copied().find(|chapter| chapter.id() == id)
+}
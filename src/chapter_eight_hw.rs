@@ -5,38 +5,116 @@
 use std::{
     any,
     collections::{btree_map::Values, HashMap},
-    io,
+    io::{self, BufRead},
     ops::Index,
 };
 
 use rand::Error;
 
-pub fn chapter_eight_hw() {
+use crate::chapter::{Chapter, Section};
+use crate::output::{say, Report};
+use crate::repl;
+
+pub fn chapter_eight_hw(out: &mut dyn Report) {
+    median_and_mode(out);
+    pig_latin(out);
+    add_employee(out);
+}
+
+pub struct ChapterEightHw;
+impl Chapter for ChapterEightHw {
+    fn number(&self) -> u8 {
+        8
+    }
+    fn title(&self) -> &'static str {
+        "Chapter 8 Homework"
+    }
+    fn summary(&self) -> &'static str {
+        "Median and mode, pig latin and an employee directory"
+    }
+    fn sections(&self) -> &'static [Section] {
+        &[
+            Section {
+                name: "median_and_mode",
+                run: median_and_mode,
+            },
+            Section {
+                name: "pig_latin",
+                run: pig_latin,
+            },
+            Section {
+                name: "add_employee",
+                run: add_employee,
+            },
+        ]
+    }
+    fn is_homework(&self) -> bool {
+        true
+    }
+    fn run(&self, out: &mut dyn Report) {
+        chapter_eight_hw(out);
+    }
+}
+
+fn median_and_mode(out: &mut dyn Report) {
     // median and mode of Vec<i32>
     let mut data = vec![0, 1, 3, 4, 5, 5, 5, 6, 9, 2, 4, 9];
     let data = Data::new(&mut data); // the vector is now sorted and stored in a Data struct
-    println!("{:?}", data);
+    say!(out, "{:?}", data);
+}
 
+fn pig_latin(out: &mut dyn Report) {
     // this gives an error
     let ig_pay_atin_lay = string_to_pig_latin(&String::from("happy birth day my dude! 😋"));
-    println!("{ig_pay_atin_lay}");
+    say!(out, "{ig_pay_atin_lay}");
     // this works
     let ig_pay_atin_lay = string_to_pig_latin(&String::from("happy birth day my dude!"));
-    println!("{ig_pay_atin_lay}");
+    say!(out, "{ig_pay_atin_lay}");
+}
 
-    add_employee();
+// translates each line typed in until exit
+pub fn pig_latin_translator(input: &mut dyn BufRead, out: &mut dyn Report) {
+    say!(
+        out,
+        "Type a sentence to translate it into pig latin, \"exit\" to stop."
+    );
+    while let Some(sentence) = repl::prompt_line(input, out, "pig latin> ") {
+        say!(out, "{}", string_to_pig_latin(&sentence));
+    }
 }
 
 // get median and mode of vector of integers by returning a data structure with the sorted vector
 // along with the median and mode
+
+/// A list of integers in sorted order along with its median and mode.
 #[derive(Debug)]
-struct Data {
-    data: Vec<i32>,
-    median: i32,
-    mode: i32,
+pub struct Data {
+    /// The values, sorted smallest first.
+    pub data: Vec<i32>,
+    /// The middle value, or the mean of the two middle values (rounded down) for an even count.
+    pub median: i32,
+    /// The most common value, the smallest one wins a tie.
+    pub mode: i32,
 }
 impl Data {
-    fn new(data_vec: &mut Vec<i32>) -> Self {
+    /// Sorts `data_vec` in place and works out its median and mode.
+    ///
+    /// ```
+    /// let mut values = vec![5, 1, 4, 4, 2];
+    /// let data = rust_book::Data::new(&mut values);
+    /// assert_eq!(data.data, vec![1, 2, 4, 4, 5]);
+    /// assert_eq!(data.median, 4);
+    /// assert_eq!(data.mode, 4);
+    ///
+    /// // the two middle values are averaged without overflowing
+    /// assert_eq!(rust_book::Data::new(&mut [i32::MAX, i32::MAX - 2]).median, i32::MAX - 1);
+    /// assert_eq!(rust_book::Data::new(&mut [i32::MIN, i32::MAX]).median, -1);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `data_vec` is empty, there is no median of nothing.
+    pub fn new(data_vec: &mut [i32]) -> Self {
         let mut map = HashMap::new();
         let mut data_mode = 0;
         let mut data_median = 0;
@@ -49,20 +127,25 @@ impl Data {
         }
 
         // find value with highest frequency (mode)
+        // ties go to the smallest value, otherwise the answer depends on the order the hashmap iterates in
         let mut count = 0;
         for (key, val) in map.iter() {
-            if val > &count {
+            if *val > count || (*val == count && **key < data_mode) {
                 data_mode = **key;
-                count += 1;
+                count = *val;
             }
         }
 
         data_vec.sort();
         let len = data_vec.len();
+        // odd lengths have a single middle value, even ones average the two either side of the middle.
+        // adding them could overflow, half the gap between them can't (the gap itself is widened, it
+        // can be bigger than an i32 when they have different signs)
         if len % 2 != 0 {
-            let data_median = ((data_vec[len / 2 - 1] + data_vec[len / 2 + 1]) as f32) / 2.0;
-        } else {
             data_median = data_vec[len / 2];
+        } else {
+            let (low, high) = (data_vec[len / 2 - 1], data_vec[len / 2]);
+            data_median = low + ((i64::from(high) - i64::from(low)) / 2) as i32;
         }
 
         Self {
@@ -74,7 +157,15 @@ impl Data {
 }
 
 // Convert strings to pig latin. The first consonant of each word is moved to the end of the word and “ay” is added, so “first” becomes “irst-fay.” Words that start with a vowel have “hay” added to the end instead (“apple” becomes “apple-hay”). Keep in mind the details about UTF-8 encoding!
-fn string_to_pig_latin(sentence: &String) -> String {
+
+/// Translates each word of `sentence` into pig latin, e.g. "first" becomes "irst-fay" and "apple" becomes "apple-hay".
+///
+/// Only ascii input is supported, anything else returns a message saying the string is invalid.
+///
+/// ```
+/// assert_eq!(rust_book::string_to_pig_latin("happy birthday"), "appy-hay irthday-bay");
+/// ```
+pub fn string_to_pig_latin(sentence: &str) -> String {
     let vowels = "aeiou";
     let mut pig_latin = String::from("");
     for slice in sentence.split_whitespace() {
@@ -107,42 +198,49 @@ fn string_to_pig_latin(sentence: &String) -> String {
 // i did not consider the cause of names with more than one word though that could most likely be
 // solved by adding another arm to the length match statement or splitting the department at the
 // intermediate word ("to") instead of relying on argument indices
-fn add_employee() {
+fn add_employee(out: &mut dyn Report) {
+    employee_directory(&mut io::stdin().lock(), out);
+}
+
+// the directory reads its commands from any input so the repl can host it too
+pub fn employee_directory(input: &mut dyn BufRead, out: &mut dyn Report) {
     let mut department = String::new();
     let mut employee_name = String::new();
     let mut employees: HashMap<String, String> = HashMap::new();
 
-    println!("Add employees and their departments.\ne.g. \"add Sally to Engineering\"\nTo view all employees and their departments type \"list all\"\nTo view all employess in a specific department type \"list department\", where \"department\" is the name of the department.\nTo exit type\"exit\"\n");
+    say!(out, "Add employees and their departments.\ne.g. \"add Sally to Engineering\"\nTo view all employees and their departments type \"list all\"\nTo view all employess in a specific department type \"list department\", where \"department\" is the name of the department.\nTo exit type\"exit\"\n");
 
     loop {
-        // clear input so that io::stdin().read_line(&mut input) doest append the next input
-        let mut input = String::from("");
-        io::stdin()
-            .read_line(&mut input)
-            .expect("failed to read input");
+        // clear line so that input.read_line(&mut line) doest append the next input
+        let mut line = String::from("");
+        let bytes_read = input.read_line(&mut line).expect("failed to read input");
+        // end of input, same as typing exit
+        if bytes_read == 0 {
+            break;
+        }
 
-        // let input = input.to_lowercase();
+        // let line = line.to_lowercase();
 
-        let arguments = input.split_whitespace().collect::<Vec<&str>>();
+        let arguments = line.split_whitespace().collect::<Vec<&str>>();
         // probably a rustier way of doing this error checking
         match arguments.len() {
             0 => continue,
             1 => match &arguments[0][..] {
                 "exit" => break,
                 _ => {
-                    println!("expected multiple parameters or \"exit\"");
+                    say!(out, "expected multiple parameters or \"exit\"");
                     continue;
                 }
             },
             2 | 3 | 4 => {}
             _ => {
-                println!("expected multiple parameters");
+                say!(out, "expected multiple parameters");
                 break;
             }
         }
 
         if arguments.len() < 2 {
-            println!("expected multiple parameters");
+            say!(out, "expected multiple parameters");
             continue;
         }
 
@@ -186,7 +284,7 @@ fn add_employee() {
                     }
                     _ => {
                         department = String::from("");
-                        println!("error! expected either \"add name to department\" or \"add name department\".");
+                        say!(out, "error! expected either \"add name to department\" or \"add name department\".");
                         continue;
                     }
                 }
@@ -194,7 +292,7 @@ fn add_employee() {
                 employees.entry(employee_name).or_insert(department);
             }
             "list" => match arguments[1] {
-                "all" => println!("{:?}", employees),
+                "all" => say!(out, "{:?}", employees),
                 _ => {
                     let department = format!(
                         "{}{}",
@@ -221,17 +319,17 @@ fn add_employee() {
                             }
                             // alphabetical sort
                             employees_in_dept.sort_by(|a, b| a.cmp(b));
-                            println!("{:?}", employees_in_dept);
+                            say!(out, "{:?}", employees_in_dept);
                         }
                         false => {
-                            println!("expected either an \"add\" or \"list\" command");
+                            say!(out, "expected either an \"add\" or \"list\" command");
                             continue;
                         }
                     }
                 }
             },
             _ => {
-                println!("expected either an \"add\" or \"list\" command");
+                say!(out, "expected either an \"add\" or \"list\" command");
                 continue;
             }
         }
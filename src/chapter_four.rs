@@ -1,3 +1,6 @@
+use crate::chapter::{Chapter, Section};
+use crate::output::{say, Report};
+
 // ownership https://doc.rust-lang.org/book/ch04-01-what-is-ownership.html
 /*
 
@@ -13,18 +16,50 @@ references
 - refs must always be valid (no danglies)
 
 */
-pub fn chapter_four() {
-    ownership();
-    references();
-    slices();
+pub fn chapter_four(out: &mut dyn Report) {
+    ownership(out);
+    references(out);
+    slices(out);
+}
+
+pub struct ChapterFour;
+impl Chapter for ChapterFour {
+    fn number(&self) -> u8 {
+        4
+    }
+    fn title(&self) -> &'static str {
+        "Understanding Ownership"
+    }
+    fn summary(&self) -> &'static str {
+        "Ownership, borrowing with references, and slices"
+    }
+    fn sections(&self) -> &'static [Section] {
+        &[
+            Section {
+                name: "ownership",
+                run: ownership,
+            },
+            Section {
+                name: "references",
+                run: references,
+            },
+            Section {
+                name: "slices",
+                run: slices,
+            },
+        ]
+    }
+    fn run(&self, out: &mut dyn Report) {
+        chapter_four(out);
+    }
 }
 
-fn ownership() {
-    vars_and_ownership();
-    functions_and_ownership();
+fn ownership(out: &mut dyn Report) {
+    vars_and_ownership(out);
+    functions_and_ownership(out);
     return_vals_and_ownership();
 
-    fn vars_and_ownership() {
+    fn vars_and_ownership(out: &mut dyn Report) {
         // scoped to the function
         let s = "hello";
         // string literal is stored on the stack since contents are known at compile time
@@ -42,7 +77,7 @@ fn ownership() {
         // ::from() requests memory
         // memory is freed when s goes out of scope
         s.push_str(", world!");
-        println!("{}", s);
+        say!(out, "{}", s);
 
         // multiple variables and moves
         // bind 5 to x
@@ -55,8 +90,7 @@ fn ownership() {
         let s2 = s1;
         // a String is made up of 3 parts, a ptr to the place in memory where the String is stored, the len of the String and the capacity.
         // copying s1 into s2 *does not* copy the actual value in memory (deep copy), it only copies the ptr, len and capacity. (kind of a shallow copy)
-        // therefore this is not valid
-        // println!("s1 = {}", s1);
+        // therefore using s1 here is not valid (quiz snippet 4 use-after-move)
         // this results in 2 variables referencing the same area in memory 🚫
         // rust fixes this by making s1 leave scope when s2 is declared (if this was not the case s1 and s2 would both try to free the same area in memory when they leave scope, causing issues) this is called a move
         // rust will never make a deep copy of your data unless explicitely told to, this is good because deep copies are memory intensive and it is good to know when you are doing so and not have it be done automatically
@@ -65,35 +99,35 @@ fn ownership() {
         // this *does* copy the heap data as well
         let s2 = s1.clone();
         // this is now valid
-        println!("s1 = {}, s2 = {}", s1, s2);
+        say!(out, "s1 = {}, s2 = {}", s1, s2);
 
         // types that annotate the *Copy* trait are stored on the stack and are thus copied/cloned and not moved. types with the Drop trait cannot annotate a  Copy trait
         // eg. all ints, bools, floats, char, tuples that only contain types that implement Copy
     }
-    fn functions_and_ownership() {
+    fn functions_and_ownership(out: &mut dyn Report) {
         // s comes into scope
         let s = String::from("hello");
         // s's value moves into the function
-        takes_ownership(s);
+        takes_ownership(out, s);
         // s is no longer valid here
 
         // x comes into scope
         let x = 5;
         // x would move into function but i32 is Copy, therefore
-        makes_copy(x);
+        makes_copy(out, x);
         // x is still valid here
     }
     // x and s go out of scope, but s was moved so nothing happens here
 
-    fn takes_ownership(some_string: String) {
+    fn takes_ownership(out: &mut dyn Report, some_string: String) {
         // some string comes into scope
-        println!("{}", some_string);
+        say!(out, "{}", some_string);
     }
     // some_string goes out of scope and `drop` is called the memory is then freed.
 
-    fn makes_copy(some_int: i32) {
+    fn makes_copy(out: &mut dyn Report, some_int: i32) {
         // some_int comes into scope
-        println!("{}", some_int);
+        say!(out, "{}", some_int);
     }
     // some_int goes out of scope
     //
@@ -126,16 +160,16 @@ fn ownership() {
 
     // assinging a value to another variable moves it. when a variable with heap data goes out of scope, drop will free the memory unless ownership of data has been moved to another variable
 }
-fn references() {
+fn references(out: &mut dyn Report) {
     // a less tedious way than manually returning and moving ownership is references
     // & allows to refer to a value without taking ownership
     // * is dereferencing but will be discussed later.
-    basic_references();
-    mutable_references();
-    fn basic_references() {
+    basic_references(out);
+    mutable_references(out);
+    fn basic_references(out: &mut dyn Report) {
         let s1 = String::from("hello");
         let len = calculate_length(&s1);
-        println!("len of '{}', is {}.", s1, len);
+        say!(out, "len of '{}', is {}.", s1, len);
     }
     fn calculate_length(s: &String) -> usize {
         // s is a reference to a String (&String), (this is why a string literal is &str)
@@ -143,40 +177,31 @@ fn references() {
     }
     // s goes out of scope,  but since calculate_length does not have ownership, it is not dropped
 
-    fn mutable_references() {
+    fn mutable_references(out: &mut dyn Report) {
         let mut s = String::from("hello");
         change(&mut s);
         // nb! if a mutable ref to a value exists, you can have no other refs to that value.
-        /* invalid! cannot borrow `s` as mutable more than once at a time
-        let r1 = &mut s;
-        let r2 = &mut s;
-        println!("{}{}",r1,r2);
-        */
+        // invalid! cannot borrow `s` as mutable more than once at a time (quiz snippet 4 double-mutable-borrow)
         // *can* have multiple mutable references in different scopes
         {
             let r1 = &mut s;
-            println!("{}", r1);
+            say!(out, "{}", r1);
         }
         let r2 = &mut s;
-        println!("{}", r2);
+        say!(out, "{}", r2);
 
         // *can* have mutiple immutable references but not if a mutable reference exists
         // immutable references do not expect value to change suddenly.so cannot exist at same time of mutable ref
-        /*
-        let r1 = &s; // no problem
-        let r2 = &s; // no problem
-        let r3 = &mut s; //BIG problem
-        println!("{}{}{}", r1, r2, r3);
-        */
+        // two immutable references are no problem, adding a mutable one is a BIG problem (quiz snippet 4 mutable-and-immutable-borrow)
 
         // a reference’s scope starts from where it is introduced and continues through the last time that reference is used.
         let mut s = String::from("hello");
         let r1 = &s;
         let r2 = &s;
-        println!("{} and {}", r1, r2);
+        say!(out, "{} and {}", r1, r2);
         // r1 and r2 are not used beyond here so the references are now out of scope making the next line okay
         let r3 = &mut s;
-        println!("{}", r3);
+        say!(out, "{}", r3);
         // if r1 and r2 were used here there would be an issue. (luckily always caught at compile time 🦀)
     }
     fn change(some_string: &mut String) {
@@ -185,12 +210,7 @@ fn references() {
 
     // rust does not allow dangling pointers/references (ptr references a location in memry that has been given to someone else)
     // compiler guarantees that res will not go out of scope befrore the reference to the data does.
-    /*
-     fn dangle() -> &String{ // returns ref to a String
-        let s = String::from("hello"); // s is a new String
-        &s // return reference to String, s
-     } // s goes out of scope, so what is &s now referencing?, this will not compile
-    */
+    // returning &s from the function that owns s will not compile, what would it be referencing once s goes out of scope? (quiz snippet 4 dangling-reference)
     // this works! because of ownership being moved out of function.
     fn no_dangle() -> String {
         let s = String::from("hello");
@@ -199,10 +219,10 @@ fn references() {
     // lifetimes will be discussed in chpt 10
 }
 
-fn slices() {
-    string_slices();
+fn slices(out: &mut dyn Report) {
+    string_slices(out);
     other_slices();
-    fn string_slices() {
+    fn string_slices(out: &mut dyn Report) {
         // long way that returns the index of first space or length if the input is a single word
         fn first_word(s: &String) -> usize {
             let bytes = s.as_bytes();
@@ -241,10 +261,8 @@ fn slices() {
         }
         let mut s = String::from("hello world");
         let word = better_first_word(&s);
-        // s = ""
-        // s.clear();
-        // trying to clear s will result in compile error since a reference to it is borrowed as immutable by the function so cannot be mutated.
-        println!("the first word is: {}", word);
+        // trying to clear s will result in compile error since a reference to it is borrowed as immutable by the function so cannot be mutated. (quiz snippet 4 clear-while-borrowed)
+        say!(out, "the first word is: {}", word);
 
         // STRING LITERALS
 
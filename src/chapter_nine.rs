@@ -1,14 +1,18 @@
 use std::{
+    fmt,
     fs::{self, File},
     io::{self, ErrorKind, Read},
     net::IpAddr,
 };
 
-pub fn chapter_nine() {
+use crate::chapter::{Chapter, Section};
+use crate::output::{say, Report};
+
+pub fn chapter_nine(out: &mut dyn Report) {
     // recoverable (report and retry): Result<T,E>
-    recoverable();
+    recoverable(out);
     // unrecoverable (panic): panic!()
-    unrecoverable();
+    unrecoverable(out);
 
     /*
     adding
@@ -16,9 +20,42 @@ pub fn chapter_nine() {
     panic = 'abort'
     to Cargo.toml will prevent unwinding (tracing back through the stack and cleaning up data from each function)
     */
-    to_panic_or_not_to_panic();
+    to_panic_or_not_to_panic(out);
 }
-fn unrecoverable() {
+
+pub struct ChapterNine;
+impl Chapter for ChapterNine {
+    fn number(&self) -> u8 {
+        9
+    }
+    fn title(&self) -> &'static str {
+        "Error Handling"
+    }
+    fn summary(&self) -> &'static str {
+        "Recoverable errors with Result, panic! and when to use which"
+    }
+    fn sections(&self) -> &'static [Section] {
+        &[
+            Section {
+                name: "recoverable",
+                run: recoverable,
+            },
+            Section {
+                name: "unrecoverable",
+                run: unrecoverable,
+            },
+            Section {
+                name: "to_panic_or_not_to_panic",
+                run: to_panic_or_not_to_panic,
+            },
+        ]
+    }
+    fn run(&self, out: &mut dyn Report) {
+        chapter_nine(out);
+    }
+}
+
+fn unrecoverable(out: &mut dyn Report) {
     //panic!("at the disco");
 
     //let v = vec![1, 2, 3];
@@ -31,7 +68,7 @@ fn unrecoverable() {
     // to exclude debug symbols compile with --release
 }
 
-fn recoverable() {
+fn recoverable(out: &mut dyn Report) {
     /*
     enum Result<T,E>{
         Ok(T),
@@ -150,7 +187,7 @@ fn main() -> Result<(), Box<dyn Error>> {
 // exe will exit with 0 if main returns Ok(()) and exit with a non zero value if it returns an Err
 //  the std::process::Termination trait contain a report fn that returns an ExitCode
 
-fn to_panic_or_not_to_panic() {
+fn to_panic_or_not_to_panic(out: &mut dyn Report) {
     // , it’s understood that a call to a method like unwrap that could panic is meant as a placeholder for the way you’d want your application to handle errors,
     //unwrap and expect methods are very handy when prototyping, before you’re ready to decide how to handle errors.
     // in tests you want the whole test to fail
@@ -177,10 +214,10 @@ fn to_panic_or_not_to_panic() {
     // use unsigned when values shouldn't be negative
     // etc
 
-    custom_types_for_validatio();
+    custom_types_for_validatio(out);
 }
 
-fn custom_types_for_validatio() {
+fn custom_types_for_validatio(out: &mut dyn Report) {
     loop {
         let guess = "12";
         // parse as signed value
@@ -190,32 +227,76 @@ fn custom_types_for_validatio() {
         };
         // check that value is positive
         if guess < 1 || guess > 100 {
-            println!("the secret number will be between 1 and 100.");
+            say!(out, "the secret number will be between 1 and 100.");
             continue;
         }
         break;
     }
 }
 // better way
+
+/// A guess for the guessing game, always between 1 and 100 (or the range it was made with).
 pub struct Guess {
     // use i32 to allow negative inputs
     value: i32,
 }
 impl Guess {
+    /// Wraps `value` after checking it is in range.
+    ///
+    /// ```
+    /// assert_eq!(rust_book::Guess::new(50).value(), 50);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `value` is not between 1 and 100.
     pub fn new(value: i32) -> Guess {
         // check that input is positive
-        if value < 1 || value > 100 {
+        match Guess::between(value, 1, 100) {
+            Ok(guess) => guess,
             // contract violated for Guess::new
-            panic!("guess must be between 1 and 100, got {}", value);
+            Err(e) => panic!("{}", e),
+        }
+    }
+    /// Wraps `value` if it is between `low` and `high` (inclusive), for games played over other
+    /// ranges. The error is for the caller to show, and ask again.
+    ///
+    /// ```
+    /// use rust_book::Guess;
+    ///
+    /// assert_eq!(Guess::between(500, 1, 1000).unwrap().value(), 500);
+    /// assert!(Guess::between(0, 1, 1000).is_err());
+    /// ```
+    pub fn between(value: i32, low: i32, high: i32) -> Result<Guess, OutOfRange> {
+        if value < low || value > high {
+            return Err(OutOfRange { value, low, high });
         }
-        Guess { value }
+        Ok(Guess { value })
     }
     // borrows self, and returns i32. (getter)
     // needed because the value field of Guess struct is private
     // keeping the value private keeps code from setting value directly without the checking that is provided by new
+    /// The guessed number.
     pub fn value(&self) -> i32 {
         self.value
     }
 }
 
+/// Why [`Guess::between`] turned a value down.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OutOfRange {
+    pub value: i32,
+    pub low: i32,
+    pub high: i32,
+}
+impl fmt::Display for OutOfRange {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "guess must be between {} and {}, got {}",
+            self.low, self.high, self.value
+        )
+    }
+}
+
 // in conclusion, rust has loads of ways to handle errors and it *really* wants you to handle them
@@ -1,5 +1,24 @@
-pub fn chapter_one(){
-    println!("This chapter is about installation and cargo");
-    println!("rustc works a bit like gcc");
-    println!("cargo makes things easier");
-}
\ No newline at end of file
+use crate::chapter::Chapter;
+use crate::output::{say, Report};
+
+pub fn chapter_one(out: &mut dyn Report) {
+    say!(out, "This chapter is about installation and cargo");
+    say!(out, "rustc works a bit like gcc");
+    say!(out, "cargo makes things easier");
+}
+
+pub struct ChapterOne;
+impl Chapter for ChapterOne {
+    fn number(&self) -> u8 {
+        1
+    }
+    fn title(&self) -> &'static str {
+        "Getting Started"
+    }
+    fn summary(&self) -> &'static str {
+        "Installing Rust, rustc and cargo"
+    }
+    fn run(&self, out: &mut dyn Report) {
+        chapter_one(out);
+    }
+}
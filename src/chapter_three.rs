@@ -1,32 +1,87 @@
 use std::{num, result};
 
+use crate::chapter::{Chapter, Section};
+use crate::output::{say, Report};
+
 // common programming concepts
 // comments should be placed on the line above line being commented on
-pub fn chapter_three() {
-    variables();
-    constants();
-    shadowing();
-    scalar_data_types();
-    operators();
-    compound_data_types();
-    functions();
-    control_flow();
+pub fn chapter_three(out: &mut dyn Report) {
+    variables(out);
+    constants(out);
+    shadowing(out);
+    scalar_data_types(out);
+    operators(out);
+    compound_data_types(out);
+    functions(out);
+    control_flow(out);
+}
+
+pub struct ChapterThree;
+impl Chapter for ChapterThree {
+    fn number(&self) -> u8 {
+        3
+    }
+    fn title(&self) -> &'static str {
+        "Common Programming Concepts"
+    }
+    fn summary(&self) -> &'static str {
+        "Variables, data types, functions and control flow"
+    }
+    fn sections(&self) -> &'static [Section] {
+        &[
+            Section {
+                name: "variables",
+                run: variables,
+            },
+            Section {
+                name: "constants",
+                run: constants,
+            },
+            Section {
+                name: "shadowing",
+                run: shadowing,
+            },
+            Section {
+                name: "scalar_data_types",
+                run: scalar_data_types,
+            },
+            Section {
+                name: "operators",
+                run: operators,
+            },
+            Section {
+                name: "compound_data_types",
+                run: compound_data_types,
+            },
+            Section {
+                name: "functions",
+                run: functions,
+            },
+            Section {
+                name: "control_flow",
+                run: control_flow,
+            },
+        ]
+    }
+    fn run(&self, out: &mut dyn Report) {
+        chapter_three(out);
+    }
 }
 
-fn variables() {
+fn variables(out: &mut dyn Report) {
     // use snake case this_is_a_var
     // variables and mutability
     let x = 5;
-    println!("x is {}", x);
+    say!(out, "x is {}", x);
     // x = 6; will panic
 
     let mut y = 5;
-    println!("y is {}", y);
+    say!(out, "y is {}", y);
     y = 6;
-    println!("y is {}", y);
+    say!(out, "y is {}", y);
 }
 
-fn constants() {
+fn constants(out: &mut dyn Report) {
     // use all caps
     // arent allowed to use mut
     // type MUST be allocated (no inference)
@@ -35,18 +90,18 @@ fn constants() {
     // within the scope they were declared
     // rust is block scoped so this const will only be valid within this function
     const THREE_HOURS_IN_SECONDS: u32 = 60 * 60 * 3;
-    println!("Three hours in seconds: {}", THREE_HOURS_IN_SECONDS);
+    say!(out, "Three hours in seconds: {}", THREE_HOURS_IN_SECONDS);
 }
 
-fn shadowing() {
+fn shadowing(out: &mut dyn Report) {
     let x = 5;
     // create new variable x taking original value.
     let x = x + 1;
     {
         let x = x * 2;
-        println!("x in the inner scope: {}", x); // 12
+        say!(out, "x in the inner scope: {}", x); // 12
     }
-    println!("x in the outer scope: {}", x); // 6
+    say!(out, "x in the outer scope: {}", x); // 6
 
     // different from mutability
     // we can reuse the variable name with different types.
@@ -62,18 +117,18 @@ fn shadowing() {
     */
 }
 
-fn scalar_data_types() {
+fn scalar_data_types(out: &mut dyn Report) {
     // rust is statically typed (must know type of all variables at compile time)
     // rust can often infer but when multiple types are possible the type must be annotated like below (could be i32, u8 etc)
     let _guess: u32 = "42".parse().expect("Not a number!");
 
     // scalar types (a single value)
-    ints();
+    ints(out);
     floats();
     bools();
-    chars();
+    chars(out);
 
-    fn ints() {
+    fn ints(out: &mut dyn Report) {
         /*
         // integers
         - signed => possible to be negative (stored as 2s complement
@@ -90,16 +145,18 @@ fn scalar_data_types() {
             - binary : 0b1111_0000
             - byte (only u8) : b'A'
         - when numbers are used without declaration you can add a type suffix: 57u8
+            - the literal module reads all of these, and says why 256u8 or 0xffi8 don't fit
         - * compiling in release mode with the --release flag, Rust does not include checks for integer overflow that cause panics. Rust used 2s complement wrapping, for a u8 256 becomes 0 and 257 becomes 1. Relying on integer overflow is an error*
             - explicitely handle wrapping
                 - wrapping_* methods
                 - return None if there is overflow using the checked_* methods
                 - return the value and a bool indicating there was overflow with the overflowing_* methods
                 - saturate at max value with saturating_* methods
+            - `rust_book overflow 254 + 2` (or overflow in the repl) shows all four for every width
         */
 
         let a: u8 = 0xfe; // 254
-        println!("{}", a + 0x1u8); // 255
+        say!(out, "{}", a + 0x1u8); // 255
     }
 
     fn floats() {
@@ -118,32 +175,33 @@ fn scalar_data_types() {
         let _t = true;
         let _f: bool = false;
     }
-    fn chars() {
+    fn chars(out: &mut dyn Report) {
         // like c, single quote for char double for str
         // chars are unicode
         let c = 'z';
         let z: char = '↯';
         let heart_cat = '😻';
-        println!("here are some chars {0}{1}{2}", c, z, heart_cat);
+        say!(out, "here are some chars {0}{1}{2}", c, z, heart_cat);
     }
 }
 
-fn operators() {
+fn operators(out: &mut dyn Report) {
+    // `eval` in the repl works these out (and any others) with the same types and rules
     let sum = 5 + 10;
-    println!("sum of 5 and 10: {}", sum);
+    say!(out, "sum of 5 and 10: {}", sum);
     let diff = 95.5 - 4.3;
-    println!("difference of 95.5 and 4.3: {}", diff);
+    say!(out, "difference of 95.5 and 4.3: {}", diff);
     let prod = 4 * 30;
-    println!("product of 4 and 30: {}", prod);
+    say!(out, "product of 4 and 30: {}", prod);
     let quotient = 56.7 / 32.2;
-    println!("quotient of 56.7 and 32.2: {}", quotient);
+    say!(out, "quotient of 56.7 and 32.2: {}", quotient);
     let trunc = -5 / 10; //-1
-    println!("truncation of -5 and 10 (-5/10): {}", trunc);
+    say!(out, "truncation of -5 and 10 (-5/10): {}", trunc);
     let remainder = 43 % 10; //3
-    println!("remander (modulus) of 43 and 10: {}", remainder);
+    say!(out, "remander (modulus) of 43 and 10: {}", remainder);
 }
 
-fn compound_data_types() {
+fn compound_data_types(out: &mut dyn Report) {
     tuples();
     arrays();
 
@@ -195,15 +253,15 @@ fn compound_data_types() {
     }
 }
 
-fn functions() {
-    parameters(15, 'm');
+fn functions(out: &mut dyn Report) {
+    parameters(out, 15, 'm');
     let x = returns();
-    println!("x: {}", x);
+    say!(out, "x: {}", x);
 
     // should be defined after main but it doesnt matter so long as functions are withing scope of call
-    fn parameters(x: i32, unit_label: char) {
+    fn parameters(out: &mut dyn Report, x: i32, unit_label: char) {
         // types MUST be declared (no inference)
-        println!("measurement: {}{}", x, unit_label);
+        say!(out, "measurement: {}{}", x, unit_label);
     }
 
     // statements: instrusctions performing an action without returning a value
@@ -216,7 +274,7 @@ fn functions() {
         let x = 3;
         x + 1
     };
-    println!("y: {}", y);
+    say!(out, "y: {}", y);
 
     // functions with returns
     // must define type of return value
@@ -230,36 +288,36 @@ fn functions() {
     }
 }
 
-fn control_flow() {
-    if_statements();
-    loops();
+fn control_flow(out: &mut dyn Report) {
+    if_statements(out);
+    loops(out);
 
-    fn if_statements() {
+    fn if_statements(out: &mut dyn Report) {
         let number = 3;
         // conditions in if statements must be bool (ie cannot do if number {})
         if number < 5 {
-            println!("true");
-            println!("number is less than 5");
+            say!(out, "true");
+            say!(out, "number is less than 5");
         } else {
-            println!("false");
-            println!("number is greater than 5");
+            say!(out, "false");
+            say!(out, "number is greater than 5");
         }
 
         if number != 0 {
-            println!("true");
-            println!("number is not equal to zero");
+            say!(out, "true");
+            say!(out, "number is not equal to zero");
         }
 
         // rust only executes up to the first 'true'
         // match is better and more clear in most cases
         if number % 4 == 0 {
-            println!("number is divisible by 4");
+            say!(out, "number is divisible by 4");
         } else if number % 3 == 0 {
-            println!("number is divisible by 4");
+            say!(out, "number is divisible by 4");
         } else if number % 2 == 0 {
-            println!("number is divisible by 4");
+            say!(out, "number is divisible by 4");
         } else {
-            println!("number is not divisible by 4, 3, or 2");
+            say!(out, "number is not divisible by 4, 3, or 2");
         }
 
         // ifs in lets
@@ -270,12 +328,12 @@ fn control_flow() {
 
         // blocks of code evaluate to last expression in them and numbers are also expressions
     }
-    fn loops() {
+    fn loops(out: &mut dyn Report) {
         // three kinds of loops loop, while, for
-        loop_loop();
-        while_loop();
-        for_loop();
-        fn loop_loop() {
+        loop_loop(out);
+        while_loop(out);
+        for_loop(out);
+        fn loop_loop(out: &mut dyn Report) {
             let mut x: u8 = 0;
             loop {
                 // loop is infinite until broken
@@ -287,7 +345,7 @@ fn control_flow() {
                     continue; // skips any remaining code in loop and loops again
                               // this loop will print 1,2,4,5 skipping 3
                 }
-                println!("x : {}", x);
+                say!(out, "x : {}", x);
             }
             // return from loop (add value you want returned after break used to stop loop)
             let mut counter = 0;
@@ -298,16 +356,16 @@ fn control_flow() {
                 }
             };
 
-            println!("The result is {result}");
+            say!(out, "The result is {result}");
 
             // nested loops and loop labels
             // break and continue apply to innermost loop at that point
             let mut count = 0;
             'counting_up: loop {
-                println!("count: {}", count);
+                say!(out, "count: {}", count);
                 let mut remaining = 10;
                 loop {
-                    println!("remaining: {}", remaining);
+                    say!(out, "remaining: {}", remaining);
                     if remaining == 9 {
                         break;
                     }
@@ -319,42 +377,42 @@ fn control_flow() {
                 count += 1;
             }
 
-            println!("end count: {}", count);
+            say!(out, "end count: {}", count);
         }
 
         // typically clearer than a loop loop
-        fn while_loop() {
+        fn while_loop(out: &mut dyn Report) {
             let mut x = 10;
             while x != 0 {
                 // calls breaks when condition evaluates to true
-                println!("I'm not finished yet!");
+                say!(out, "I'm not finished yet!");
                 x -= 1;
             }
             // looping through a collection with while
             let a = [10, 20, 30, 40, 50];
             let mut idx = 0;
             while idx < 5 {
-                println!("a[{0}] = {1}", idx, a[idx]);
+                say!(out, "a[{0}] = {1}", idx, a[idx]);
                 idx += 1;
             }
         }
 
         // most used loop, safe and concise.
-        fn for_loop() {
+        fn for_loop(out: &mut dyn Report) {
             // _ is unused just loops for 5 times
             for _ in 1..6 {
-                println!("I'm not finished yet!");
+                say!(out, "I'm not finished yet!");
             }
 
             // in reverse?
             for number in (1..6).rev() {
-                println!("{number}");
+                say!(out, "{number}");
             }
 
             // looping through a collection with for
             let a = [10, 20, 30, 40, 50];
             for element in a {
-                println!("a = {element}")
+                say!(out, "a = {element}")
             }
         }
     }
@@ -1,9 +1,117 @@
+use std::io::BufRead;
+
+use crate::chapter::{Chapter, Section};
+use crate::output::{say, Report};
+use crate::repl;
+use crate::song;
+use crate::temperature::{Celsius, Fahrenheit, Temperature, TemperatureError};
+
+pub fn chapter_three_hw(out: &mut dyn Report) {
+    temperature(out);
+    fibonacci(out);
+    twelve_days_of_xmas(out);
+}
+
+pub struct ChapterThreeHw;
+impl Chapter for ChapterThreeHw {
+    fn number(&self) -> u8 {
+        3
+    }
+    fn title(&self) -> &'static str {
+        "Chapter 3 Homework"
+    }
+    fn summary(&self) -> &'static str {
+        "Temperature conversion, Fibonacci numbers and The Twelve Days of Christmas"
+    }
+    fn sections(&self) -> &'static [Section] {
+        &[
+            Section {
+                name: "f_to_c",
+                run: temperature,
+            },
+            Section {
+                name: "fib_n",
+                run: fibonacci,
+            },
+            Section {
+                name: "twelve_days_of_xmas",
+                run: twelve_days_of_xmas,
+            },
+        ]
+    }
+    fn is_homework(&self) -> bool {
+        true
+    }
+    fn run(&self, out: &mut dyn Report) {
+        chapter_three_hw(out);
+    }
+}
+
+fn temperature(out: &mut dyn Report) {
+    let f: f32 = 112.0;
+    let c: f32 = f_to_c(f);
+    say!(out, "{}℉ = {:.2}℃", f, c);
+}
+
+// converts each temperature typed in until exit, to ℃ or ℉ whichever it wasn't in
+// (kelvin goes to ℃ and rankine to ℉, the scales with the same size of degree)
+pub fn temperature_converter(input: &mut dyn BufRead, out: &mut dyn Report) {
+    say!(
+        out,
+        "Type a temperature like 112F, -40 °C or 300K to convert it (a plain number is ℉), \"exit\" to stop."
+    );
+    while let Some(line) = repl::prompt_line(input, out, "temperature> ") {
+        let temperature = match line.parse::<Temperature>() {
+            Err(TemperatureError::MissingUnit(_)) => {
+                line.parse::<Fahrenheit>().map(Temperature::from)
+            }
+            parsed => parsed,
+        };
+        match temperature {
+            Ok(Temperature::Fahrenheit(f)) => say!(out, "{f} = {:.2}", Celsius::from(f)),
+            Ok(Temperature::Celsius(c)) => say!(out, "{c} = {:.2}", Fahrenheit::from(c)),
+            Ok(Temperature::Kelvin(k)) => say!(out, "{k} = {:.2}", Celsius::from(k)),
+            Ok(Temperature::Rankine(r)) => say!(out, "{r} = {:.2}", Fahrenheit::from(r)),
+            Err(e) => say!(out, "{e}"),
+        }
+    }
+}
+
+fn fibonacci(out: &mut dyn Report) {
+    let fibs: Vec<i32> = fib_n(10);
+    say!(out, "{:?}", fibs);
+}
+
+// the conversions just return their results, printing is left to the caller
+
+/// Converts a temperature in degrees Fahrenheit to degrees Celsius.
+///
+/// This is the bare number version, see the `temperature` module for typed temperatures on
+/// every scale that also rule out anything below absolute zero, and `units` for lengths, masses,
+/// volumes and times.
+///
+/// ```
+/// assert_eq!(rust_book::f_to_c(32.0), 0.0);
+/// assert_eq!(rust_book::f_to_c(-40.0), -40.0);
+/// ```
 pub fn f_to_c(f: f32) -> f32 {
     let c: f32 = (f - 32.0) * 5.0 / 9.0;
-    println!("{}℉ = {:.2}℃", f, c);
     c
 }
 
+/// Returns the first `n` Fibonacci numbers starting from 0.
+///
+/// `fib_n(0)` is `[0]` and any `n` below 3 (including negative ones) gives `[0, 1]`.
+///
+/// ```
+/// assert_eq!(rust_book::fib_n(10), vec![0, 1, 1, 2, 3, 5, 8, 13, 21, 34]);
+/// ```
+///
+/// # Panics
+///
+/// The terms are `i32`, so past 47 of them the addition overflows (a panic in debug builds).
+/// The `fibonacci` module has versions that return an error instead, hold more with `u64` or
+/// `u128`, or never overflow with big integers.
 pub fn fib_n(n: i32) -> Vec<i32> {
     let mut fibs: Vec<i32> = vec![0];
 
@@ -17,38 +125,16 @@ pub fn fib_n(n: i32) -> Vec<i32> {
             fibs.push(fibs[fibs.len() - 1] + fibs[fibs.len() - 2]);
         }
     }
-    println!("{:?}", fibs);
     fibs
 }
 
-pub fn twelve_days_of_xmas() {
-    let things: [&str; 12] = [
-        "A partridge in a pear tree",
-        "turtle doves",
-        "French hens",
-        "calling birds",
-        "gold rings",
-        "geese a-laying",
-        "swans a-swimming",
-        "maids a-milking",
-        "ladies dancing",
-        "lords a-leaping",
-        "pipers piping",
-        "drummers drumming",
-    ];
-    for n in 1..=12 {
-        if n == 1 {
-            println!("On the {}st day of Christmas My true love sent to me.", n);
-        } else if n == 2 {
-            println!("On the {}nd day of Christmas My true love sent to me.", n);
-        } else if n == 3 {
-            println!("On the {}rd day of Christmas My true love sent to me.", n);
-        } else {
-            println!("On the {}th day of Christmas My true love sent to me.", n);
-        }
-        for day in (1..n).rev() {
-            println!("{} {}", day + 1, things[day]);
+// the words are in songs/twelve_days.song now, see the `song` module for the other songs and for
+// printing them as markdown or html
+pub fn twelve_days_of_xmas(out: &mut dyn Report) {
+    let song = song::builtin("twelve-days").unwrap();
+    for verse in song.verses() {
+        for line in verse {
+            say!(out, "{}", line);
         }
-        println!("{}", things[0]);
     }
 }
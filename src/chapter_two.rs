@@ -2,7 +2,10 @@
 
 // bring in the input/output library into scope
 // some functions from std are always brought in (called the prelude)
-use std::{cmp::Ordering, io};
+use std::{
+    cmp::Ordering,
+    io::{self, BufRead},
+};
 
 // need to add rand to Cargo.toml and run cargo install or cargo build
 // Cargo lock ensures reproducible build by storing specific versions used in the project
@@ -10,11 +13,20 @@ use std::{cmp::Ordering, io};
 // crates.io is a site where you can browse and find crates.
 use rand::Rng;
 
+use crate::chapter::Chapter;
+use crate::output::{say, Report};
+
 // new public function (accessible from main.rs)
-pub fn chapter_two() {
+pub fn chapter_two(out: &mut dyn Report) {
+    guessing_game(&mut io::stdin().lock(), out);
+}
+
+// the game reads its guesses from any input (stdin above) so the repl can host it too
+// guessing_game.rs has the same game as a GuessingGame type with a seedable rng, for testing and reuse
+pub fn guessing_game(input: &mut dyn BufRead, out: &mut dyn Report) {
     // guessing game
     // macro of the fmt function that prints to screen
-    println!("Guess the number!");
+    say!(out, "Guess the number!");
 
     // thread_rng is the specific generator provided by rand
     // .gen_range takes in range to provide 1..100 is excluesive of 100, 1..=100 is inclusive of upper and lower bounds
@@ -24,7 +36,7 @@ pub fn chapter_two() {
 
     // loop is infinite until broken or panic
     loop {
-        println!("Please input your guess.");
+        say!(out, "Please input your guess.");
         // all vars in rust are immutable unless declared with mut as mutable
         // type can often be inferred but needs to be known at compile time (i.e. static typing)
         // String is growable UTF-8 encoded text
@@ -32,7 +44,7 @@ pub fn chapter_two() {
         // ::new() indicates that new is a function associated with the String type
         let mut guess = String::new();
         // user input
-        io::stdin()
+        input
             // calls read_line method on stdin, &mut guess tells function to store input inside guess (must be mutable)
             // read_line appends to string and does not overwrite contents
             // & indicate a reference which allows multiple parts of the code to access one
@@ -47,13 +59,24 @@ pub fn chapter_two() {
             // without .expect compiler will error but still compile
             .expect("Failed to read line");
 
+        // read_line returns Ok(0) at the end of input (e.g. ctrl-d or a piped file), stop instead of looping forever
+        if guess.is_empty() {
+            say!(out, "No more input, the number was {secret_number}.");
+            break;
+        }
+        // giving up
+        if matches!(guess.trim(), "quit" | "exit") {
+            say!(out, "The number was {secret_number}.");
+            break;
+        }
+
         // using println!
         /*
         let x = 5;
         let y = 10;
         println!("x = {x}, and y + 2 = {}", y + 2);
          */
-        println!("You guessed: {guess}");
+        say!(out, "You guessed: {}", guess.trim_end());
 
         // rust allows to shadow the previous value of a variable with a new one.
         // lets use reuse the variable name as a different type instead of creating a new variable name like guess_str and guess_int
@@ -72,13 +95,29 @@ pub fn chapter_two() {
         // .cmp returns a variant of the Ordering enum whicha
         // .cmp assumes both are same type
         match guess.cmp(&secret_number) {
-            Ordering::Less => println!("Too small."),
-            Ordering::Greater => println!("Too big."),
+            Ordering::Less => say!(out, "Too small."),
+            Ordering::Greater => say!(out, "Too big."),
             Ordering::Equal => {
-                println!("You win!");
+                say!(out, "You win!");
                 // exit loop once won
                 break;
             }
         }
     }
 }
+
+pub struct ChapterTwo;
+impl Chapter for ChapterTwo {
+    fn number(&self) -> u8 {
+        2
+    }
+    fn title(&self) -> &'static str {
+        "Programming a Guessing Game"
+    }
+    fn summary(&self) -> &'static str {
+        "A first program: input, match, Ordering and the rand crate"
+    }
+    fn run(&self, out: &mut dyn Report) {
+        chapter_two(out);
+    }
+}
@@ -0,0 +1,553 @@
+// command line for running the chapters without editing main.rs each time
+// usage:
+//   rust_book list
+//   rust_book run <chapter> [section]   e.g. run 4, run 4 slices, run 3-hw, run all
+//   rust_book run 4 --format markdown --output chapter_four.md
+
+use std::{
+    ops::RangeInclusive,
+    panic::{self, AssertUnwindSafe},
+    path::PathBuf,
+    str::FromStr,
+};
+
+use crate::golden;
+use rust_book::bigint::{BigUint, ParseBigUintError};
+use rust_book::chapter::{self, Chapter, CHAPTERS};
+use rust_book::fibonacci::{self, Recurrence};
+use rust_book::guessing_game::{self, Difficulty, Feedback, Settings};
+use rust_book::multiplayer::{self, Mode, ServerSettings};
+use rust_book::output::{Format, Report};
+use rust_book::overflow::Expression;
+use rust_book::progress::Entry;
+use rust_book::table::{Steps, Style};
+use rust_book::units::{self, Unit};
+use rust_book::{snippets, solver, song, storage};
+
+pub const USAGE: &str = "usage:
+    rust_book list                       list the chapters and their sections
+    rust_book run <chapter> [section]    run one chapter, e.g. `run 4`, `run 4 slices` or `run 3-hw`
+    rust_book run all                    run every chapter in order
+    rust_book progress                   show which chapters you've run and what to study next
+    rust_book play [easy|normal|hard]    play the guessing game from chapter 2 (normal by default)
+    rust_book leaderboard [difficulty]   the best guessing game players for each difficulty
+    rust_book solve [strategy]           let the computer play the guessing game with binary, random, linear
+                                         and learned-median search and compare how many guesses they need
+    rust_book serve [turns|race]         host a guessing game on localhost for several players (turns by default)
+    rust_book join <name>                join a game started with `serve`
+    rust_book convert <expression>       convert between units, e.g. `convert 5 ft 3 in to cm` or `convert 3h to s`
+    rust_book fib <n>                    the nth fibonacci number, however big (add --mod <m> for the remainder)
+    rust_book sequence <name|c1,c2,...>  terms of fibonacci, lucas, tribonacci, pell or a recurrence of your own,
+                                         e.g. `sequence 1,1 --initial 2,1` is the lucas numbers
+    rust_book table <from> [to...]       a conversion table counting up in one unit, e.g. `table F C K` or `table ft m`
+                                         (every other unit of the same kind if none are given)
+    rust_book overflow <a> <op> <b>      what every integer type makes of a sum that might overflow, e.g.
+                                         `overflow 250 + 10`, `overflow MAX * 2` or `overflow neg MIN`
+    rust_book sing <song|file.song>      the words of a cumulative song: twelve-days, old-macdonald,
+                                         house-that-jack-built or one of your own (see songs/)
+    rust_book quiz [chapter]             guess whether the examples from the notes compile, panic or run
+    rust_book verify [chapter]           compile the quiz snippets with rustc and check they do what the notes say
+    rust_book repl                       start an interactive shell for the guessing game and other exercises
+    rust_book bless [dir]                regenerate the golden output snapshots (in tests/snapshots by default,
+                                         run it from the repository)
+    rust_book help                       show this message
+
+options for run:
+    --format <plain|markdown|json>       how to render the output (default plain)
+    --output <file>                      write the output to a file instead of the terminal
+
+options for play:
+    --range <low..=high>                 guess a number in this range instead
+    --attempts <n>                       how many guesses you get
+    --feedback <classic|hot-cold|liar[:chance]>
+                                         too small/too big, hotter/colder than the last guess,
+                                         or too small/too big but sometimes wrong (20% by default)
+    --player <name>                      the name to put on the leaderboard (default your user name)
+
+options for serve and join:
+    --port <n>                           the port on 127.0.0.1 to use (default 7878)
+    --players <n>                        serve: how many players to wait for (default 2)
+    --range <low..=high>                 serve: the range to guess in (default 1..=100)
+
+options for table:
+    --start <n>, --stop <n>, --step <n>  the values of the first column (default 0 to 100 in steps of 10)
+    --precision <n>                      decimal places (default 2)
+    --format <plain|csv|markdown>        how to write the table (default plain)
+    --output <file>                      write the table to a file instead of the terminal
+
+options for sing:
+    --format <plain|markdown|html>       how to write the words (default plain)
+    --output <file>                      write them to a file instead of the terminal
+
+options for fib and sequence:
+    --mod <m>                            fib: only the remainder after dividing by m, for any size of n
+    --initial <a,b,...>                  sequence: the starting terms, one for each coefficient
+    --count <n>                          sequence: how many terms to print (default 20)
+
+options for solve:
+    --range <low..=high>                 the range to guess in (default 1..=100)
+    --games <n>                          how many games each strategy plays (default 10000)
+    --seed <n>                           seed for picking the secrets, the same seed gives the same games";
+
+#[derive(Debug, PartialEq)]
+pub enum Command {
+    List,
+    Run(Target, RunOptions),
+    Progress,
+    Play(Settings, String),
+    Leaderboard(Option<Difficulty>),
+    Solve(SolveOptions),
+    Serve(ServerSettings, u16),
+    Join(String, u16),
+    Table(TableOptions),
+    Convert(String),
+    Overflow(Expression),
+    Fib(BigUint, Option<u64>),
+    Sequence(Recurrence, usize),
+    // a built in song or the path to a .song file
+    Sing(String, song::Style, Option<PathBuf>),
+    Quiz(Option<String>),
+    Verify(Option<String>),
+    Repl,
+    // the snapshot directory
+    Bless(PathBuf),
+    Help,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Target {
+    All,
+    Chapter(String),
+    Section(String, String),
+}
+
+#[derive(Debug, PartialEq)]
+pub struct RunOptions {
+    pub format: Format,
+    pub output: Option<PathBuf>,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct SolveOptions {
+    // None to compare all of them
+    pub strategy: Option<String>,
+    pub range: RangeInclusive<i32>,
+    pub games: usize,
+    pub seed: u64,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct TableOptions {
+    pub from: &'static Unit,
+    pub to: Vec<&'static Unit>,
+    pub steps: Steps,
+    pub style: Style,
+    pub precision: usize,
+    pub output: Option<PathBuf>,
+}
+
+impl RunOptions {
+    // plain output to the terminal is printed as it happens, anything else is captured first
+    pub fn is_streamed(&self) -> bool {
+        self.format == Format::Plain && self.output.is_none()
+    }
+}
+
+pub fn parse(args: &[String]) -> Result<Command, String> {
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+    // the command comes first, its options can be anywhere after it
+    let (args, mut options) = match args.split_first() {
+        Some((command, rest)) => {
+            let (rest, options) = Options::parse(command, rest)?;
+            ([&[*command][..], &rest].concat(), options)
+        }
+        None => (args, Options::default()),
+    };
+
+    let command = match args[..] {
+        [] | ["help"] | ["-h"] | ["--help"] => Command::Help,
+        ["list"] => Command::List,
+        ["progress"] => Command::Progress,
+        ["play"] => Command::Play(
+            game_settings(Difficulty::Normal, &mut options)?,
+            player_option(&mut options),
+        ),
+        ["play", difficulty] => Command::Play(
+            game_settings(difficulty.parse()?, &mut options)?,
+            player_option(&mut options),
+        ),
+        ["leaderboard"] | ["scores"] => Command::Leaderboard(None),
+        ["leaderboard", difficulty] | ["scores", difficulty] => {
+            Command::Leaderboard(Some(difficulty.parse()?))
+        }
+        ["solve"] => Command::Solve(solve_options(None, &mut options)?),
+        ["solve", name] => {
+            if solver::strategy(name).is_none() {
+                let names: Vec<&str> = solver::strategies().iter().map(|s| s.name()).collect();
+                return Err(format!(
+                    "no strategy called \"{name}\", expected one of {}",
+                    names.join(", ")
+                ));
+            }
+            Command::Solve(solve_options(Some(name.to_string()), &mut options)?)
+        }
+        ["serve"] => Command::Serve(
+            server_settings(Mode::Turns, &mut options)?,
+            port_option(&mut options)?,
+        ),
+        ["serve", mode] => Command::Serve(
+            server_settings(mode.parse()?, &mut options)?,
+            port_option(&mut options)?,
+        ),
+        ["join", name] => Command::Join(name.to_string(), port_option(&mut options)?),
+        ["join"] => {
+            return Err(String::from(
+                "expected a name to play as, e.g. `join ferris`",
+            ))
+        }
+        ["table", from, ref to @ ..] => {
+            let from = unit(from)?;
+            let to = match to {
+                // everything else that measures the same thing
+                [] => units::of(from.dimension)
+                    .filter(|unit| *unit != from)
+                    .collect(),
+                to => to.iter().map(|name| unit(name)).collect::<Result<_, _>>()?,
+            };
+            Command::Table(TableOptions {
+                from,
+                to,
+                steps: Steps::new(
+                    options.number("--start")?.unwrap_or(0.0),
+                    options.number("--stop")?.unwrap_or(100.0),
+                    options.number("--step")?.unwrap_or(10.0),
+                )?,
+                style: options
+                    .take("--format")
+                    .map_or(Ok(Style::Plain), str::parse)?,
+                precision: options.number("--precision")?.unwrap_or(2),
+                output: options.take("--output").map(PathBuf::from),
+            })
+        }
+        ["fib", n] => Command::Fib(
+            n.parse().map_err(|e: ParseBigUintError| e.to_string())?,
+            options.number("--mod")?,
+        ),
+        ["sequence", rule] => {
+            let initial = options.take("--initial").map(number_list).transpose()?;
+            let recurrence = match (Recurrence::named(rule), initial) {
+                (Some(named), None) => Ok(named),
+                (Some(named), Some(initial)) => named.starting_with(initial),
+                (None, Some(initial)) => Recurrence::new(number_list(rule)?, initial),
+                // coefficients need their starting terms
+                (None, None) => {
+                    return Err(format!(
+                        "no sequence called \"{rule}\", expected one of {} or coefficients with --initial",
+                        fibonacci::NAMED.join(", ")
+                    ))
+                }
+            };
+            Command::Sequence(
+                recurrence.map_err(|e| e.to_string())?,
+                options.number("--count")?.unwrap_or(20),
+            )
+        }
+        ["fib"] | ["sequence"] => {
+            return Err(format!(
+                "expected {}, try `rust_book help`",
+                match args[0] {
+                    "fib" => "which fibonacci number, e.g. `fib 100`",
+                    _ => "a sequence, e.g. `sequence lucas`",
+                }
+            ))
+        }
+        ["overflow", ref expression @ ..] if !expression.is_empty() => {
+            Command::Overflow(expression.join(" ").parse()?)
+        }
+        ["overflow"] => {
+            return Err(String::from(
+                "expected a sum to try, e.g. `overflow 250 + 10` or `overflow MAX * 2`",
+            ))
+        }
+        ["sing", name] => Command::Sing(
+            name.to_string(),
+            options
+                .take("--format")
+                .map_or(Ok(song::Style::Plain), str::parse)?,
+            options.take("--output").map(PathBuf::from),
+        ),
+        ["sing"] => {
+            let names: Vec<&str> = song::SONGS.iter().map(|(name, _)| *name).collect();
+            return Err(format!(
+                "expected a song, one of {} or a .song file",
+                names.join(", ")
+            ));
+        }
+        ["convert", ref expression @ ..] if !expression.is_empty() => {
+            Command::Convert(expression.join(" "))
+        }
+        ["convert"] => {
+            return Err(String::from(
+                "expected something to convert, e.g. `convert 5 ft 3 in to cm`",
+            ))
+        }
+        ["table"] => {
+            return Err(String::from(
+                "expected the unit to count in and the ones to convert to, e.g. `table F C`",
+            ))
+        }
+        ["quiz"] => Command::Quiz(None),
+        ["quiz", id] => Command::Quiz(Some(snippet_chapter(id)?)),
+        ["verify"] => Command::Verify(None),
+        ["verify", id] => Command::Verify(Some(snippet_chapter(id)?)),
+        ["repl"] | ["shell"] => Command::Repl,
+        ["bless"] => Command::Bless(PathBuf::from(golden::DEFAULT_DIR)),
+        ["bless", dir] => Command::Bless(PathBuf::from(dir)),
+        ["run", "all"] => Command::Run(Target::All, run_options(&mut options)?),
+        ["run", id] => {
+            find(id)?;
+            Command::Run(Target::Chapter(id.to_string()), run_options(&mut options)?)
+        }
+        ["run", id, section] => match find(id)?.section(section) {
+            Some(_) => Command::Run(
+                Target::Section(id.to_string(), section.to_string()),
+                run_options(&mut options)?,
+            ),
+            None => {
+                return Err(format!(
+                    "chapter {id} has no section called \"{section}\", try `rust_book list`"
+                ))
+            }
+        },
+        ["run"] => return Err(String::from("expected a chapter to run, e.g. `run 4`")),
+        _ => return Err(format!("unrecognised command \"{}\"", args.join(" "))),
+    };
+    options.finish()?;
+    Ok(command)
+}
+
+// --format and --output for run
+fn run_options(options: &mut Options) -> Result<RunOptions, String> {
+    Ok(RunOptions {
+        format: options
+            .take("--format")
+            .map_or(Ok(Format::Plain), str::parse)?,
+        output: options.take("--output").map(PathBuf::from),
+    })
+}
+
+// the `--name value` pairs after the command. each command takes the ones it understands and any
+// left over are a mistake, rather than something to quietly ignore
+#[derive(Default)]
+struct Options<'a> {
+    command: &'a str,
+    // the name, the value and whether the command has taken it
+    given: Vec<(&'a str, &'a str, bool)>,
+}
+
+impl<'a> Options<'a> {
+    // splits the options from the other arguments, the word after an option is always its value
+    // (so `--output --format` writes to a file called --format)
+    fn parse(command: &'a str, args: &[&'a str]) -> Result<(Vec<&'a str>, Self), String> {
+        let mut rest = Vec::new();
+        let mut given = Vec::new();
+        let mut args = args.iter();
+        while let Some(arg) = args.next() {
+            if !arg.starts_with("--") {
+                rest.push(*arg);
+                continue;
+            }
+            let value = args
+                .next()
+                .ok_or_else(|| format!("{arg} expects a value"))?;
+            if given.iter().any(|(name, _, _)| name == arg) {
+                return Err(format!("{arg} is given more than once"));
+            }
+            given.push((*arg, *value, false));
+        }
+        Ok((rest, Options { command, given }))
+    }
+
+    fn take(&mut self, name: &str) -> Option<&'a str> {
+        self.given
+            .iter_mut()
+            .find(|(given, _, _)| *given == name)
+            .map(|(_, value, taken)| {
+                *taken = true;
+                *value
+            })
+    }
+
+    // `--name n` for a number
+    fn number<T: FromStr>(&mut self, name: &str) -> Result<Option<T>, String> {
+        self.take(name)
+            .map(|n| {
+                n.parse()
+                    .map_err(|_| format!("{name} expects a number, got \"{n}\""))
+            })
+            .transpose()
+    }
+
+    fn finish(&self) -> Result<(), String> {
+        match self.given.iter().find(|(_, _, taken)| !taken) {
+            Some((name, _, _)) => Err(format!("{} doesn't take {name}", self.command)),
+            None => Ok(()),
+        }
+    }
+}
+
+// "1,-2,3" for --initial and the coefficients of a sequence
+fn number_list(list: &str) -> Result<Vec<i128>, String> {
+    list.split(',')
+        .map(|n| {
+            n.trim()
+                .parse()
+                .map_err(|_| format!("\"{n}\" in \"{list}\" isn't a whole number"))
+        })
+        .collect()
+}
+
+// --range, --games and --seed
+fn solve_options(strategy: Option<String>, options: &mut Options) -> Result<SolveOptions, String> {
+    Ok(SolveOptions {
+        strategy,
+        range: solve_range(range_option(options)?)?,
+        games: options.number("--games")?.unwrap_or(10_000),
+        seed: options.number("--seed")?.unwrap_or(1),
+    })
+}
+
+fn solve_range(range: Option<RangeInclusive<i32>>) -> Result<RangeInclusive<i32>, String> {
+    let range = range.unwrap_or(1..=100);
+    if range.is_empty() {
+        return Err(format!(
+            "the range {}..={} is empty",
+            range.start(),
+            range.end()
+        ));
+    }
+    Ok(range)
+}
+
+fn find(id: &str) -> Result<&'static dyn Chapter, String> {
+    chapter::find(id).ok_or_else(|| format!("no chapter called \"{id}\", try `rust_book list`"))
+}
+
+// the name for the leaderboard, your user name unless --player says otherwise
+fn player_option(options: &mut Options) -> String {
+    options
+        .take("--player")
+        .map(str::to_string)
+        .unwrap_or_else(storage::current_user)
+}
+
+// --range for play, solve and serve
+fn range_option(options: &mut Options) -> Result<Option<RangeInclusive<i32>>, String> {
+    options
+        .take("--range")
+        .map(guessing_game::parse_range)
+        .transpose()
+}
+
+// a preset with --range, --attempts and --feedback applied on top
+fn game_settings(difficulty: Difficulty, options: &mut Options) -> Result<Settings, String> {
+    let preset = difficulty.settings();
+    let settings = Settings::new(
+        range_option(options)?.unwrap_or(preset.range),
+        options.number("--attempts")?.or(preset.max_attempts),
+    )?;
+    let feedback = options
+        .take("--feedback")
+        .map(str::parse::<Feedback>)
+        .transpose()?;
+    Ok(settings.with_feedback(feedback.unwrap_or(preset.feedback)))
+}
+
+// --range and --players for serve
+fn server_settings(mode: Mode, options: &mut Options) -> Result<ServerSettings, String> {
+    let range = range_option(options)?;
+    let players = options.number("--players")?.unwrap_or(2);
+    if players == 0 {
+        return Err(String::from("--players must be at least 1"));
+    }
+    Ok(ServerSettings {
+        range: solve_range(range)?,
+        mode,
+        players,
+    })
+}
+
+fn port_option(options: &mut Options) -> Result<u16, String> {
+    Ok(options
+        .number("--port")?
+        .unwrap_or(multiplayer::DEFAULT_PORT))
+}
+
+fn unit(name: &str) -> Result<&'static Unit, String> {
+    units::find(name).ok_or_else(|| format!("unknown unit \"{name}\""))
+}
+
+// a chapter that has snippets in the catalog
+fn snippet_chapter(id: &str) -> Result<String, String> {
+    find(id)?;
+    if snippets::from_chapter(Some(id)).is_empty() {
+        return Err(format!("chapter {id} has no quiz snippets"));
+    }
+    Ok(id.to_string())
+}
+
+pub fn list() {
+    for chapter in CHAPTERS {
+        println!(
+            "{:>5}  {} - {}",
+            chapter.id(),
+            chapter.title(),
+            chapter.summary()
+        );
+        for section in chapter.sections() {
+            println!("         {}", section.name);
+        }
+    }
+}
+
+// runs the chapters one after another, a panicking chapter is reported and the rest still run
+// returns what was run and whether it passed so it can be added to the progress file
+pub fn run(target: &Target, out: &mut dyn Report) -> Vec<Entry> {
+    match target {
+        Target::All => CHAPTERS
+            .iter()
+            .map(|chapter| run_chapter(*chapter, out))
+            .collect(),
+        Target::Chapter(id) => chapter::find(id)
+            .map(|chapter| run_chapter(chapter, out))
+            .into_iter()
+            .collect(),
+        Target::Section(id, name) => chapter::find(id)
+            .and_then(|chapter| Some((chapter, chapter.section(name)?)))
+            .map(|(chapter, section)| {
+                out.heading(&format!(
+                    "Chapter {}: {} ({name})",
+                    chapter.id(),
+                    chapter.title()
+                ));
+                let ok = panic::catch_unwind(AssertUnwindSafe(|| (section.run)(out))).is_ok();
+                report(&chapter.id(), Some(section.name), ok)
+            })
+            .into_iter()
+            .collect(),
+    }
+}
+
+fn run_chapter(chapter: &dyn Chapter, out: &mut dyn Report) -> Entry {
+    out.heading(&format!("Chapter {}: {}", chapter.id(), chapter.title()));
+    // catch_unwind lets us keep going after a panic (the panic message is still printed)
+    let ok = panic::catch_unwind(AssertUnwindSafe(|| chapter.run(out))).is_ok();
+    report(&chapter.id(), None, ok)
+}
+
+fn report(id: &str, section: Option<&str>, ok: bool) -> Entry {
+    if !ok {
+        eprintln!("chapter {id} failed");
+    }
+    Entry::now(id, section, ok)
+}
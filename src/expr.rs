@@ -0,0 +1,618 @@
+// arithmetic the way rust does it, with types: integer division truncates towards zero, the
+// remainder takes the sign of the left hand side, floats are ieee and mixing types is an error
+//
+//   eval> -5 / 10
+//   0: i32
+//   eval> -7 % 3
+//   -1: i32
+//   eval> 56.7f32 / 32.2
+//   1.7608695: f32
+//   eval> 1u8 + 1i32
+//   cannot add `i32` to `u8`
+//   eval> -1i8 as u8 * 2
+//   attempt to multiply with overflow
+//
+// a number without a suffix takes its type from the other side or an integer `as` (so 300 as u8
+// doesn't fit), or is an i32 (f64 with a decimal point) if nothing says otherwise. overflowing is
+// an error like a panic in a debug build.
+// the numbers are read by the literal module, so 0xff, 0b1010_u8 and b'A' work too
+
+use std::{fmt, io::BufRead, iter::Peekable, str::Chars, str::FromStr};
+
+use crate::literal::{Literal, LiteralError};
+use crate::output::{say, Report};
+use crate::repl;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Type {
+    I8,
+    I16,
+    I32,
+    I64,
+    I128,
+    Isize,
+    U8,
+    U16,
+    U32,
+    U64,
+    U128,
+    Usize,
+    F32,
+    F64,
+}
+
+impl Type {
+    pub const ALL: [Type; 14] = [
+        Type::I8,
+        Type::I16,
+        Type::I32,
+        Type::I64,
+        Type::I128,
+        Type::Isize,
+        Type::U8,
+        Type::U16,
+        Type::U32,
+        Type::U64,
+        Type::U128,
+        Type::Usize,
+        Type::F32,
+        Type::F64,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Type::I8 => "i8",
+            Type::I16 => "i16",
+            Type::I32 => "i32",
+            Type::I64 => "i64",
+            Type::I128 => "i128",
+            Type::Isize => "isize",
+            Type::U8 => "u8",
+            Type::U16 => "u16",
+            Type::U32 => "u32",
+            Type::U64 => "u64",
+            Type::U128 => "u128",
+            Type::Usize => "usize",
+            Type::F32 => "f32",
+            Type::F64 => "f64",
+        }
+    }
+
+    pub fn is_float(&self) -> bool {
+        matches!(self, Type::F32 | Type::F64)
+    }
+
+    pub fn is_unsigned(&self) -> bool {
+        matches!(
+            self,
+            Type::U8 | Type::U16 | Type::U32 | Type::U64 | Type::U128 | Type::Usize
+        )
+    }
+}
+
+impl FromStr for Type {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Type::ALL
+            .into_iter()
+            .find(|ty| ty.name() == s)
+            .ok_or_else(|| format!("unknown type `{s}`"))
+    }
+}
+
+impl fmt::Display for Type {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+// a number and its type
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Value {
+    I8(i8),
+    I16(i16),
+    I32(i32),
+    I64(i64),
+    I128(i128),
+    Isize(isize),
+    U8(u8),
+    U16(u16),
+    U32(u32),
+    U64(u64),
+    U128(u128),
+    Usize(usize),
+    F32(f32),
+    F64(f64),
+}
+
+// any value widened so it can be cast to anything else, `as` between these and the target gives
+// the same answer as casting directly
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum Wide {
+    Signed(i128),
+    Unsigned(u128),
+    Float(f64),
+}
+
+impl Value {
+    pub fn ty(&self) -> Type {
+        match self {
+            Value::I8(_) => Type::I8,
+            Value::I16(_) => Type::I16,
+            Value::I32(_) => Type::I32,
+            Value::I64(_) => Type::I64,
+            Value::I128(_) => Type::I128,
+            Value::Isize(_) => Type::Isize,
+            Value::U8(_) => Type::U8,
+            Value::U16(_) => Type::U16,
+            Value::U32(_) => Type::U32,
+            Value::U64(_) => Type::U64,
+            Value::U128(_) => Type::U128,
+            Value::Usize(_) => Type::Usize,
+            Value::F32(_) => Type::F32,
+            Value::F64(_) => Type::F64,
+        }
+    }
+
+    fn wide(self) -> Wide {
+        match self {
+            Value::I8(n) => Wide::Signed(n.into()),
+            Value::I16(n) => Wide::Signed(n.into()),
+            Value::I32(n) => Wide::Signed(n.into()),
+            Value::I64(n) => Wide::Signed(n.into()),
+            Value::I128(n) => Wide::Signed(n),
+            Value::Isize(n) => Wide::Signed(n as i128),
+            Value::U8(n) => Wide::Unsigned(n.into()),
+            Value::U16(n) => Wide::Unsigned(n.into()),
+            Value::U32(n) => Wide::Unsigned(n.into()),
+            Value::U64(n) => Wide::Unsigned(n.into()),
+            Value::U128(n) => Wide::Unsigned(n),
+            Value::Usize(n) => Wide::Unsigned(n as u128),
+            Value::F32(n) => Wide::Float(n.into()),
+            Value::F64(n) => Wide::Float(n),
+        }
+    }
+
+    // `self as ty`: integers wrap, floats saturate and round towards zero, NaN becomes 0
+    pub fn cast(self, ty: Type) -> Value {
+        macro_rules! cast {
+            ($($ty:ident $t:ty),+) => {
+                match ty {
+                    $(Type::$ty => Value::$ty(match self.wide() {
+                        Wide::Signed(n) => n as $t,
+                        Wide::Unsigned(n) => n as $t,
+                        Wide::Float(n) => n as $t,
+                    }),)+
+                }
+            };
+        }
+        cast!(I8 i8, I16 i16, I32 i32, I64 i64, I128 i128, Isize isize, U8 u8, U16 u16, U32 u32,
+            U64 u64, U128 u128, Usize usize, F32 f32, F64 f64)
+    }
+
+    // a whole number as `ty`, None when it doesn't fit (or ty is a float)
+    pub(crate) fn fit(n: Wide, ty: Type) -> Option<Value> {
+        macro_rules! fit {
+            ($($ty:ident $t:ty),+) => {
+                match (ty, n) {
+                    $(
+                        (Type::$ty, Wide::Signed(n)) => <$t>::try_from(n).ok().map(Value::$ty),
+                        (Type::$ty, Wide::Unsigned(n)) => <$t>::try_from(n).ok().map(Value::$ty),
+                    )+
+                    _ => None,
+                }
+            };
+        }
+        fit!(I8 i8, I16 i16, I32 i32, I64 i64, I128 i128, Isize isize, U8 u8, U16 u16, U32 u32,
+            U64 u64, U128 u128, Usize usize)
+    }
+}
+
+// floats with {:?} so 1.0 keeps its point
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Value::I8(n) => write!(f, "{n}"),
+            Value::I16(n) => write!(f, "{n}"),
+            Value::I32(n) => write!(f, "{n}"),
+            Value::I64(n) => write!(f, "{n}"),
+            Value::I128(n) => write!(f, "{n}"),
+            Value::Isize(n) => write!(f, "{n}"),
+            Value::U8(n) => write!(f, "{n}"),
+            Value::U16(n) => write!(f, "{n}"),
+            Value::U32(n) => write!(f, "{n}"),
+            Value::U64(n) => write!(f, "{n}"),
+            Value::U128(n) => write!(f, "{n}"),
+            Value::Usize(n) => write!(f, "{n}"),
+            Value::F32(n) => write!(f, "{n:?}"),
+            Value::F64(n) => write!(f, "{n:?}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BinOp {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Rem,
+}
+
+impl BinOp {
+    // what rustc says when the two sides have different types
+    fn mismatch(&self, left: Ty, right: Ty) -> String {
+        match self {
+            BinOp::Add => format!("cannot add `{right}` to `{left}`"),
+            BinOp::Sub => format!("cannot subtract `{right}` from `{left}`"),
+            BinOp::Mul => format!("cannot multiply `{left}` by `{right}`"),
+            BinOp::Div => format!("cannot divide `{left}` by `{right}`"),
+            BinOp::Rem => {
+                format!("cannot calculate the remainder of `{left}` divided by `{right}`")
+            }
+        }
+    }
+}
+
+// a type as far as it's known: {integer} and {float} are literals waiting to be told
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Ty {
+    Known(Type),
+    Integer,
+    Float,
+}
+
+impl Ty {
+    // what's left unknown at the end gets rust's defaults
+    pub fn or_default(self) -> Type {
+        match self {
+            Ty::Known(ty) => ty,
+            Ty::Integer => Type::I32,
+            Ty::Float => Type::F64,
+        }
+    }
+}
+
+impl fmt::Display for Ty {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Ty::Known(ty) => write!(f, "{ty}"),
+            Ty::Integer => write!(f, "{{integer}}"),
+            Ty::Float => write!(f, "{{float}}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExprError {
+    Syntax(String),
+    // the two sides of an operator have different types
+    Mismatch(String),
+    // -x for an unsigned type
+    Negate(Type),
+    // a literal that doesn't lex, or is too big for its type like 256u8
+    Literal(LiteralError),
+    // what a debug build would panic with: overflow and dividing by zero
+    Panic(&'static str),
+}
+
+impl fmt::Display for ExprError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ExprError::Syntax(message) | ExprError::Mismatch(message) => write!(f, "{message}"),
+            ExprError::Negate(ty) => write!(f, "cannot apply unary operator `-` to type `{ty}`"),
+            ExprError::Literal(e) => write!(f, "{e}"),
+            ExprError::Panic(message) => write!(f, "{message}"),
+        }
+    }
+}
+
+impl std::error::Error for ExprError {}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expr {
+    Literal(Literal),
+    Neg(Box<Expr>),
+    Binary(BinOp, Box<Expr>, Box<Expr>),
+    Cast(Box<Expr>, Type),
+}
+
+impl FromStr for Expr {
+    type Err = ExprError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parser = Parser {
+            tokens: lex(s)?.into_iter().peekable(),
+        };
+        let expr = parser.sum()?;
+        match parser.tokens.next() {
+            None => Ok(expr),
+            Some(token) => Err(ExprError::Syntax(format!("unexpected `{token}`"))),
+        }
+    }
+}
+
+impl Expr {
+    // the type the expression ends up with, before the defaults
+    pub fn ty(&self) -> Result<Ty, ExprError> {
+        match self {
+            Expr::Literal(literal) => Ok(literal.ty()),
+            Expr::Neg(expr) => match expr.ty()? {
+                Ty::Known(ty) if ty.is_unsigned() => Err(ExprError::Negate(ty)),
+                ty => Ok(ty),
+            },
+            Expr::Binary(op, left, right) => {
+                let (left, right) = (left.ty()?, right.ty()?);
+                match (left, right) {
+                    _ if left == right => Ok(left),
+                    (Ty::Integer, Ty::Known(ty)) | (Ty::Known(ty), Ty::Integer)
+                        if !ty.is_float() =>
+                    {
+                        Ok(Ty::Known(ty))
+                    }
+                    (Ty::Float, Ty::Known(ty)) | (Ty::Known(ty), Ty::Float) if ty.is_float() => {
+                        Ok(Ty::Known(ty))
+                    }
+                    _ => Err(ExprError::Mismatch(op.mismatch(left, right))),
+                }
+            }
+            Expr::Cast(expr, ty) => {
+                // an integer literal with nothing else to go on takes its type from an integer
+                // `as`, so -1 as u32 is negating a u32
+                if expr.ty()? == Ty::Integer && ty.is_unsigned() && expr.negates() {
+                    return Err(ExprError::Negate(*ty));
+                }
+                Ok(Ty::Known(*ty))
+            }
+        }
+    }
+
+    // a minus outside of any cast, which is what an unsigned type can't have
+    fn negates(&self) -> bool {
+        match self {
+            Expr::Neg(_) => true,
+            Expr::Binary(_, left, right) => left.negates() || right.negates(),
+            _ => false,
+        }
+    }
+
+    pub fn evaluate(&self) -> Result<Value, ExprError> {
+        let ty = self.ty()?.or_default();
+        self.evaluate_as(ty)
+    }
+
+    // with the types already checked, so `ty` is what every literal in here turns into
+    fn evaluate_as(&self, ty: Type) -> Result<Value, ExprError> {
+        match self {
+            Expr::Literal(literal) => literal.value_as(ty, false).map_err(ExprError::Literal),
+            // -128i8 is fine even though 128i8 isn't
+            Expr::Neg(expr) if matches!(**expr, Expr::Literal(_)) && !ty.is_unsigned() => {
+                let Expr::Literal(literal) = &**expr else {
+                    unreachable!()
+                };
+                literal.value_as(ty, true).map_err(ExprError::Literal)
+            }
+            Expr::Neg(expr) => negate(expr.evaluate_as(ty)?),
+            Expr::Binary(op, left, right) => {
+                arithmetic(*op, left.evaluate_as(ty)?, right.evaluate_as(ty)?)
+            }
+            // and 300 as u8 is a u8 literal that doesn't fit
+            Expr::Cast(expr, to) => match expr.ty()? {
+                Ty::Integer if !to.is_float() => expr.evaluate_as(*to),
+                _ => Ok(expr.evaluate()?.cast(*to)),
+            },
+        }
+    }
+}
+
+fn negate(value: Value) -> Result<Value, ExprError> {
+    let overflow = ExprError::Panic("attempt to negate with overflow");
+    Ok(match value {
+        Value::I8(n) => Value::I8(n.checked_neg().ok_or(overflow)?),
+        Value::I16(n) => Value::I16(n.checked_neg().ok_or(overflow)?),
+        Value::I32(n) => Value::I32(n.checked_neg().ok_or(overflow)?),
+        Value::I64(n) => Value::I64(n.checked_neg().ok_or(overflow)?),
+        Value::I128(n) => Value::I128(n.checked_neg().ok_or(overflow)?),
+        Value::Isize(n) => Value::Isize(n.checked_neg().ok_or(overflow)?),
+        Value::F32(n) => Value::F32(-n),
+        Value::F64(n) => Value::F64(-n),
+        unsigned => return Err(ExprError::Negate(unsigned.ty())),
+    })
+}
+
+fn arithmetic(op: BinOp, left: Value, right: Value) -> Result<Value, ExprError> {
+    macro_rules! arithmetic {
+        (ints: $($int:ident),+; floats: $($float:ident),+) => {
+            match (left, right) {
+                $((Value::$int(a), Value::$int(b)) => Ok(Value::$int(match op {
+                    BinOp::Add => a.checked_add(b).ok_or(ExprError::Panic("attempt to add with overflow"))?,
+                    BinOp::Sub => a.checked_sub(b).ok_or(ExprError::Panic("attempt to subtract with overflow"))?,
+                    BinOp::Mul => a.checked_mul(b).ok_or(ExprError::Panic("attempt to multiply with overflow"))?,
+                    BinOp::Div if b == 0 => return Err(ExprError::Panic("attempt to divide by zero")),
+                    BinOp::Div => a.checked_div(b).ok_or(ExprError::Panic("attempt to divide with overflow"))?,
+                    BinOp::Rem if b == 0 => {
+                        return Err(ExprError::Panic("attempt to calculate the remainder with a divisor of zero"))
+                    }
+                    BinOp::Rem => a.checked_rem(b).ok_or(ExprError::Panic("attempt to calculate the remainder with overflow"))?,
+                })),)+
+                // floats never fail, 1.0 / 0.0 is inf and 0.0 / 0.0 is NaN
+                $((Value::$float(a), Value::$float(b)) => Ok(Value::$float(match op {
+                    BinOp::Add => a + b,
+                    BinOp::Sub => a - b,
+                    BinOp::Mul => a * b,
+                    BinOp::Div => a / b,
+                    BinOp::Rem => a % b,
+                })),)+
+                (left, right) => unreachable!("{} and {} should have been caught by ty()", left.ty(), right.ty()),
+            }
+        };
+    }
+    arithmetic!(ints: I8, I16, I32, I64, I128, Isize, U8, U16, U32, U64, U128, Usize; floats: F32, F64)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Number(Expr),
+    Op(char),
+    Open,
+    Close,
+    As,
+    Type(Type),
+}
+
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Token::Number(Expr::Literal(literal)) => write!(f, "{literal}"),
+            Token::Number(_) => write!(f, "number"),
+            Token::Op(op) => write!(f, "{op}"),
+            Token::Open => write!(f, "("),
+            Token::Close => write!(f, ")"),
+            Token::As => write!(f, "as"),
+            Token::Type(ty) => write!(f, "{ty}"),
+        }
+    }
+}
+
+fn lex(s: &str) -> Result<Vec<Token>, ExprError> {
+    let mut tokens = Vec::new();
+    let mut chars = s.chars().peekable();
+    while let Some(&c) = chars.peek() {
+        match c {
+            c if c.is_whitespace() => {
+                chars.next();
+            }
+            '+' | '-' | '*' | '/' | '%' => {
+                chars.next();
+                tokens.push(Token::Op(c));
+            }
+            '(' => {
+                chars.next();
+                tokens.push(Token::Open);
+            }
+            ')' => {
+                chars.next();
+                tokens.push(Token::Close);
+            }
+            '0'..='9' => tokens.push(Token::Number(literal(&mut chars)?)),
+            // b'A', before it's taken for the start of a word
+            'b' if chars.clone().nth(1) == Some('\'') => {
+                tokens.push(Token::Number(literal(&mut chars)?))
+            }
+            c if c.is_alphabetic() || c == '_' => {
+                let word = take_while(&mut chars, |c| c.is_alphanumeric() || c == '_');
+                tokens.push(match word.as_str() {
+                    "as" => Token::As,
+                    word => Token::Type(word.parse().map_err(ExprError::Syntax)?),
+                });
+            }
+            c => return Err(ExprError::Syntax(format!("unexpected `{c}`"))),
+        }
+    }
+    Ok(tokens)
+}
+
+fn take_while(chars: &mut Peekable<Chars>, keep: impl Fn(char) -> bool) -> String {
+    let mut taken = String::new();
+    while let Some(&c) = chars.peek() {
+        if !keep(c) {
+            break;
+        }
+        taken.push(c);
+        chars.next();
+    }
+    taken
+}
+
+fn literal(chars: &mut Peekable<Chars>) -> Result<Expr, ExprError> {
+    Literal::lex(chars)
+        .map(Expr::Literal)
+        .map_err(ExprError::Literal)
+}
+
+// sum := product (('+' | '-') product)*
+// product := cast (('*' | '/' | '%') cast)*
+// cast := unary ('as' type)*
+// unary := '-' unary | number | '(' sum ')'
+struct Parser<I: Iterator<Item = Token>> {
+    tokens: Peekable<I>,
+}
+
+impl<I: Iterator<Item = Token>> Parser<I> {
+    fn sum(&mut self) -> Result<Expr, ExprError> {
+        let mut expr = self.product()?;
+        while let Some(Token::Op(c @ ('+' | '-'))) = self.tokens.peek() {
+            let op = if *c == '+' { BinOp::Add } else { BinOp::Sub };
+            self.tokens.next();
+            expr = Expr::Binary(op, Box::new(expr), Box::new(self.product()?));
+        }
+        Ok(expr)
+    }
+
+    fn product(&mut self) -> Result<Expr, ExprError> {
+        let mut expr = self.cast()?;
+        while let Some(Token::Op(c @ ('*' | '/' | '%'))) = self.tokens.peek() {
+            let op = match c {
+                '*' => BinOp::Mul,
+                '/' => BinOp::Div,
+                _ => BinOp::Rem,
+            };
+            self.tokens.next();
+            expr = Expr::Binary(op, Box::new(expr), Box::new(self.cast()?));
+        }
+        Ok(expr)
+    }
+
+    fn cast(&mut self) -> Result<Expr, ExprError> {
+        let mut expr = self.unary()?;
+        while self.tokens.next_if_eq(&Token::As).is_some() {
+            match self.tokens.next() {
+                Some(Token::Type(ty)) => expr = Expr::Cast(Box::new(expr), ty),
+                _ => {
+                    return Err(ExprError::Syntax(String::from(
+                        "expected a type after `as`",
+                    )))
+                }
+            }
+        }
+        Ok(expr)
+    }
+
+    fn unary(&mut self) -> Result<Expr, ExprError> {
+        match self.tokens.next() {
+            Some(Token::Op('-')) => Ok(Expr::Neg(Box::new(self.unary()?))),
+            Some(Token::Number(number)) => Ok(number),
+            Some(Token::Open) => {
+                let expr = self.sum()?;
+                match self.tokens.next() {
+                    Some(Token::Close) => Ok(expr),
+                    _ => Err(ExprError::Syntax(String::from("expected `)`"))),
+                }
+            }
+            Some(token) => Err(ExprError::Syntax(format!(
+                "expected a number, found `{token}`"
+            ))),
+            None => Err(ExprError::Syntax(String::from(
+                "expected a number, found the end",
+            ))),
+        }
+    }
+}
+
+// the expression typed in and its value
+pub fn evaluate(text: &str) -> Result<Value, ExprError> {
+    text.parse::<Expr>()?.evaluate()
+}
+
+// a calculator in the repl that answers like rust would
+pub fn calculator(input: &mut dyn BufRead, out: &mut dyn Report) {
+    say!(
+        out,
+        "Type some arithmetic like -5 / 10, 56.7f32 / 32.2, 0xff_u8 - b'A' or 200u8 as i8 and see what rust makes of it, \"exit\" to stop."
+    );
+    while let Some(line) = repl::prompt_line(input, out, "eval> ") {
+        match evaluate(&line) {
+            Ok(value) => say!(out, "{value}: {}", value.ty()),
+            Err(e) => say!(out, "{e}"),
+        }
+    }
+}
@@ -0,0 +1,387 @@
+// fibonacci numbers that don't overflow. the homework's fib_n keeps its i32s (and its quirks), these
+// return exactly the first n numbers and say so when they won't fit instead of panicking
+//
+//   fib_n_u64(94)            the most a u64 can hold, F(93) = 12200160415121876738
+//   fib_n_big(10_000)        every one exactly, F(9999) has 2090 digits
+//   nth_fib(1_000_000)       just the one number, without the 999_999 before it
+//   fib_mod(10^100, 1000)    the last three digits of a number far too big to work out
+//
+// and other sequences where each term is a sum of multiples of the ones before (Recurrence)
+//
+//   rust_book fib 1000 --mod 7
+//   rust_book sequence lucas --count 10
+//   rust_book sequence 2,1 --initial 0,1       (the pell numbers)
+
+use std::{collections::VecDeque, fmt};
+
+use crate::bigint::BigUint;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FibError {
+    Negative(i32),
+    // asked for more numbers than the type can hold
+    Overflow {
+        asked: usize,
+        max: usize,
+        type_name: &'static str,
+    },
+    ZeroModulus,
+    // a recurrence needs one starting term for each coefficient
+    Mismatch {
+        coefficients: usize,
+        initial: usize,
+    },
+}
+
+impl fmt::Display for FibError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FibError::Negative(n) => write!(f, "can't have {n} fibonacci numbers"),
+            FibError::Overflow {
+                asked,
+                max,
+                type_name,
+            } => write!(
+                f,
+                "{asked} fibonacci numbers don't fit in {type_name}, it holds the first {max}"
+            ),
+            FibError::ZeroModulus => write!(f, "can't take a remainder after dividing by 0"),
+            FibError::Mismatch {
+                coefficients: 0, ..
+            } => write!(f, "a recurrence needs at least one coefficient"),
+            FibError::Mismatch {
+                coefficients,
+                initial,
+            } => write!(
+                f,
+                "a recurrence with {coefficients} coefficients needs {coefficients} starting terms, got {initial}"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for FibError {}
+
+// the number types fibonacci numbers can be made of
+pub trait Term: Clone {
+    fn zero() -> Self;
+    fn one() -> Self;
+    // None when the sum doesn't fit
+    fn checked_add(&self, other: &Self) -> Option<Self>;
+}
+
+macro_rules! term {
+    ($($t:ty),+) => {
+        $(
+            impl Term for $t {
+                fn zero() -> Self {
+                    0
+                }
+                fn one() -> Self {
+                    1
+                }
+                fn checked_add(&self, other: &Self) -> Option<Self> {
+                    <$t>::checked_add(*self, *other)
+                }
+            }
+        )+
+    };
+}
+
+term!(i32, i64, i128, u32, u64, u128, usize);
+
+impl Term for BigUint {
+    fn zero() -> Self {
+        BigUint::zero()
+    }
+    fn one() -> Self {
+        BigUint::one()
+    }
+    fn checked_add(&self, other: &Self) -> Option<Self> {
+        Some(self + other)
+    }
+}
+
+// 0, 1, 1, 2, 3, 5, ... one at a time, ending before the first one that doesn't fit in T
+// (BigUint never ends)
+//
+//   Fibonacci::<u64>::new().take_while(|n| *n < 100).sum::<u64>()
+pub struct Fibonacci<T> {
+    // the next two numbers, None once they've overflowed
+    a: Option<T>,
+    b: Option<T>,
+}
+
+impl<T: Term> Fibonacci<T> {
+    pub fn new() -> Self {
+        Fibonacci {
+            a: Some(T::zero()),
+            b: Some(T::one()),
+        }
+    }
+}
+
+impl<T: Term> Default for Fibonacci<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Term> Iterator for Fibonacci<T> {
+    type Item = T;
+    fn next(&mut self) -> Option<T> {
+        let a = self.a.take()?;
+        let next = self.b.as_ref().and_then(|b| b.checked_add(&a));
+        self.a = self.b.take();
+        self.b = next;
+        Some(a)
+    }
+}
+
+// the first n numbers, or where T runs out
+fn first<T: Term>(n: usize) -> Result<Vec<T>, FibError> {
+    let fibs: Vec<T> = Fibonacci::new().take(n).collect();
+    if fibs.len() < n {
+        return Err(FibError::Overflow {
+            asked: n,
+            max: fibs.len(),
+            type_name: std::any::type_name::<T>(),
+        });
+    }
+    Ok(fibs)
+}
+
+// fib_n with the negative and overflowing cases as errors, holds the first 47 numbers
+pub fn try_fib_n(n: i32) -> Result<Vec<i32>, FibError> {
+    let n = usize::try_from(n).map_err(|_| FibError::Negative(n))?;
+    first(n)
+}
+
+// the first 94 numbers fit
+pub fn fib_n_u64(n: usize) -> Result<Vec<u64>, FibError> {
+    first(n)
+}
+
+// the first 187 numbers fit
+pub fn fib_n_u128(n: usize) -> Result<Vec<u128>, FibError> {
+    first(n)
+}
+
+// never overflows, but F(n) has about n / 5 digits so it takes a while for big n
+pub fn fib_n_big(n: usize) -> Vec<BigUint> {
+    Fibonacci::new().take(n).collect()
+}
+
+// F(n) by fast doubling, from F(k) and F(k + 1):
+//   F(2k)     = F(k) * (2 * F(k + 1) - F(k))
+//   F(2k + 1) = F(k)^2 + F(k + 1)^2
+// going through the bits of n from the top, doubling k each time and adding 1 where the bit is set,
+// so it takes about log2(n) steps instead of n
+pub fn nth_fib(n: u64) -> BigUint {
+    let (mut a, mut b) = (BigUint::zero(), BigUint::one());
+    for bit in (0..u64::BITS - n.leading_zeros()).rev() {
+        let double = &a * &(&(&b + &b) - &a);
+        let double_plus_one = &(&a * &a) + &(&b * &b);
+        if n >> bit & 1 == 1 {
+            b = &double + &double_plus_one;
+            a = double_plus_one;
+        } else {
+            a = double;
+            b = double_plus_one;
+        }
+    }
+    a
+}
+
+// fibonacci numbers mod m repeat, and the length of the cycle is the pisano period. it always
+// starts again with 0, 1 and is at most 6m long, so finding it takes up to 6m steps (fib_mod
+// doesn't need it)
+//
+//   mod 2:  0 1 1 | 0 1 1 | ...     period 3
+//   mod 3:  0 1 1 2 0 2 2 1 | ...   period 8
+pub fn pisano_period(m: u64) -> Result<u64, FibError> {
+    if m == 0 {
+        return Err(FibError::ZeroModulus);
+    }
+    if m == 1 {
+        return Ok(1);
+    }
+    let (mut a, mut b) = (0, 1);
+    let mut period = 0;
+    loop {
+        (a, b) = (b, add_mod(a, b, m));
+        period += 1;
+        if (a, b) == (0, 1) {
+            return Ok(period);
+        }
+    }
+}
+
+// moduli up to this go round the pisano period first, it is at most 6m long so finding it is quick
+const PERIOD_LIMIT: u64 = 1 << 16;
+
+// F(n) mod m for any n and m, fast doubling over the bits of n with everything taken mod m, which
+// is a couple of steps per bit. for small m, n is taken mod the pisano period first, so only the
+// bits of the remainder are left to go through
+pub fn fib_mod(n: &BigUint, m: u64) -> Result<u64, FibError> {
+    let bits = if m <= PERIOD_LIMIT {
+        BigUint::from(n.rem_u64(pisano_period(m)?)).bits()
+    } else {
+        n.bits()
+    };
+    let (mut a, mut b) = (0, 1 % m);
+    for bit in bits {
+        // 2b - a, with m added so it can't go below 0
+        let double = mul_mod(a, add_mod(add_mod(b, b, m), m - a, m), m);
+        let double_plus_one = add_mod(mul_mod(a, a, m), mul_mod(b, b, m), m);
+        if bit {
+            b = add_mod(double, double_plus_one, m);
+            a = double_plus_one;
+        } else {
+            a = double;
+            b = double_plus_one;
+        }
+    }
+    Ok(a)
+}
+
+fn add_mod(a: u64, b: u64, m: u64) -> u64 {
+    ((u128::from(a) + u128::from(b)) % u128::from(m)) as u64
+}
+
+fn mul_mod(a: u64, b: u64, m: u64) -> u64 {
+    ((u128::from(a) * u128::from(b)) % u128::from(m)) as u64
+}
+
+// a(n) = c1 * a(n - 1) + c2 * a(n - 2) + ... + ck * a(n - k), from k starting terms
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Recurrence {
+    coefficients: Vec<i128>,
+    initial: Vec<i128>,
+}
+
+// the ones with names, for the command line
+pub const NAMED: [&str; 4] = ["fibonacci", "lucas", "tribonacci", "pell"];
+
+impl Recurrence {
+    pub fn new(coefficients: Vec<i128>, initial: Vec<i128>) -> Result<Self, FibError> {
+        if coefficients.is_empty() || coefficients.len() != initial.len() {
+            return Err(FibError::Mismatch {
+                coefficients: coefficients.len(),
+                initial: initial.len(),
+            });
+        }
+        Ok(Recurrence {
+            coefficients,
+            initial,
+        })
+    }
+
+    pub fn fibonacci() -> Self {
+        Recurrence::new(vec![1, 1], vec![0, 1]).unwrap()
+    }
+
+    // like fibonacci but starting 2, 1
+    pub fn lucas() -> Self {
+        Recurrence::new(vec![1, 1], vec![2, 1]).unwrap()
+    }
+
+    // each term is the sum of the three before
+    pub fn tribonacci() -> Self {
+        Recurrence::new(vec![1, 1, 1], vec![0, 0, 1]).unwrap()
+    }
+
+    pub fn pell() -> Self {
+        Recurrence::new(vec![2, 1], vec![0, 1]).unwrap()
+    }
+
+    pub fn named(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "fibonacci" | "fib" => Some(Recurrence::fibonacci()),
+            "lucas" => Some(Recurrence::lucas()),
+            "tribonacci" => Some(Recurrence::tribonacci()),
+            "pell" => Some(Recurrence::pell()),
+            _ => None,
+        }
+    }
+
+    // the same rule from other starting terms
+    pub fn starting_with(self, initial: Vec<i128>) -> Result<Self, FibError> {
+        Recurrence::new(self.coefficients, initial)
+    }
+
+    // the terms as long as they fit in an i128
+    pub fn terms(&self) -> Terms<'_> {
+        Terms {
+            recurrence: self,
+            window: VecDeque::new(),
+            n: 0,
+            overflowed: false,
+        }
+    }
+}
+
+// a(n) = a(n-1) + a(n-2), starting 0, 1
+impl fmt::Display for Recurrence {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut rule = String::new();
+        for (i, c) in self.coefficients.iter().enumerate() {
+            if *c == 0 {
+                continue;
+            }
+            let sign = match (rule.is_empty(), *c < 0) {
+                (true, true) => "-",
+                (true, false) => "",
+                (false, true) => " - ",
+                (false, false) => " + ",
+            };
+            let times = match c.unsigned_abs() {
+                1 => String::new(),
+                c => format!("{c}"),
+            };
+            rule.push_str(&format!("{sign}{times}a(n-{})", i + 1));
+        }
+        if rule.is_empty() {
+            rule.push('0');
+        }
+        let initial: Vec<String> = self.initial.iter().map(i128::to_string).collect();
+        write!(f, "a(n) = {rule}, starting {}", initial.join(", "))
+    }
+}
+
+pub struct Terms<'a> {
+    recurrence: &'a Recurrence,
+    // the last k terms, oldest first
+    window: VecDeque<i128>,
+    n: usize,
+    overflowed: bool,
+}
+
+impl Iterator for Terms<'_> {
+    type Item = i128;
+    fn next(&mut self) -> Option<i128> {
+        if self.overflowed {
+            return None;
+        }
+        let k = self.recurrence.coefficients.len();
+        let term = match self.recurrence.initial.get(self.n) {
+            Some(term) => Some(*term),
+            None => self
+                .recurrence
+                .coefficients
+                .iter()
+                .zip(self.window.iter().rev())
+                .try_fold(0i128, |sum, (c, a)| sum.checked_add(c.checked_mul(*a)?)),
+        };
+        let Some(term) = term else {
+            self.overflowed = true;
+            return None;
+        };
+        self.window.push_back(term);
+        if self.window.len() > k {
+            self.window.pop_front();
+        }
+        self.n += 1;
+        Some(term)
+    }
+}
@@ -0,0 +1,113 @@
+// golden output snapshots: each case runs a chapter (or one section of it) into a Capture and
+// compares the plain text against tests/snapshots/<name>.txt
+// after an intentional change to a chapter's output, regenerate them with `cargo run -- bless`
+// this belongs to the binary (for bless) and tests/golden.rs, which includes it with #[path], so
+// it isn't part of the library
+// the interactive chapters (2 and the employee directory) and chapter nine (which touches the
+// file system) are left out
+
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use rust_book::chapter;
+use rust_book::output::{Capture, Format};
+
+// where bless writes unless it's told otherwise, relative to where it's run from (the repository)
+pub const DEFAULT_DIR: &str = "tests/snapshots";
+
+pub struct Case {
+    pub name: &'static str,
+    pub chapter: &'static str,
+    pub section: Option<&'static str>,
+}
+
+pub const CASES: &[Case] = &[
+    Case {
+        name: "chapter_one",
+        chapter: "1",
+        section: None,
+    },
+    Case {
+        name: "chapter_three",
+        chapter: "3",
+        section: None,
+    },
+    Case {
+        name: "chapter_three_hw",
+        chapter: "3-hw",
+        section: None,
+    },
+    Case {
+        name: "chapter_four",
+        chapter: "4",
+        section: None,
+    },
+    Case {
+        name: "chapter_five",
+        chapter: "5",
+        section: None,
+    },
+    Case {
+        name: "chapter_six",
+        chapter: "6",
+        section: None,
+    },
+    Case {
+        name: "chapter_eight",
+        chapter: "8",
+        section: None,
+    },
+    Case {
+        name: "chapter_eight_hw_median_and_mode",
+        chapter: "8-hw",
+        section: Some("median_and_mode"),
+    },
+    Case {
+        name: "chapter_eight_hw_pig_latin",
+        chapter: "8-hw",
+        section: Some("pig_latin"),
+    },
+];
+
+pub fn snapshot_path(dir: &Path, case: &Case) -> PathBuf {
+    dir.join(format!("{}.txt", case.name))
+}
+
+// what the case prints today
+pub fn render(case: &Case) -> String {
+    let chapter = chapter::find(case.chapter).expect("golden case for an unknown chapter");
+    let mut capture = Capture::new();
+    match case.section {
+        Some(name) => {
+            let section = chapter
+                .section(name)
+                .expect("golden case for an unknown section");
+            (section.run)(&mut capture);
+        }
+        None => chapter.run(&mut capture),
+    }
+    capture.render(Format::Plain)
+}
+
+// rewrites every snapshot in dir with the current output, returns the files written. dir has to
+// exist already, so running it from the wrong place doesn't scatter snapshots about
+pub fn bless(dir: &Path) -> io::Result<Vec<PathBuf>> {
+    if !dir.is_dir() {
+        return Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!(
+                "no snapshot directory at {}, run bless from the repository or give the directory",
+                dir.display()
+            ),
+        ));
+    }
+    let mut written = Vec::new();
+    for case in CASES {
+        let path = snapshot_path(dir, case);
+        fs::write(&path, render(case))?;
+        written.push(path);
+    }
+    Ok(written)
+}
@@ -0,0 +1,602 @@
+// the guessing game from chapter 2 with more options: difficulty presets or a custom range, a limited
+// number of attempts (so it can be lost), other kinds of feedback, hints that cost an attempt,
+// feedback on bad input and a summary at the end
+// chapter_two keeps the original annotated version from the book
+//
+//   rust_book play hard
+//   rust_book play --range 1..=500 --attempts 8 --feedback hot-cold
+
+use std::{
+    cmp::Ordering,
+    fmt,
+    io::BufRead,
+    ops::RangeInclusive,
+    path::Path,
+    str::FromStr,
+    time::{Duration, Instant},
+};
+
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use crate::chapter_nine::{Guess, OutOfRange};
+use crate::output::{say, Report};
+use crate::repl::prompt_line;
+use crate::{scores, storage};
+
+pub const PROMPT: &str = "guess> ";
+// what a hint costs, in attempts
+pub const HINT_COST: u32 = 1;
+// the hints in the order they are given, after "it's even/odd"
+const DIVISORS: [i32; 5] = [3, 4, 5, 7, 10];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Difficulty {
+    Easy,
+    Normal,
+    Hard,
+}
+
+impl Difficulty {
+    pub const ALL: [Difficulty; 3] = [Difficulty::Easy, Difficulty::Normal, Difficulty::Hard];
+
+    pub fn settings(&self) -> Settings {
+        let (range, max_attempts) = match self {
+            Difficulty::Easy => (1..=10, None),
+            Difficulty::Normal => (1..=100, Some(10)),
+            Difficulty::Hard => (1..=1000, Some(10)),
+        };
+        Settings {
+            range,
+            max_attempts,
+            feedback: Feedback::Classic,
+        }
+    }
+}
+
+impl FromStr for Difficulty {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "easy" => Ok(Difficulty::Easy),
+            "normal" | "medium" => Ok(Difficulty::Normal),
+            "hard" => Ok(Difficulty::Hard),
+            _ => Err(format!(
+                "unknown difficulty \"{s}\", expected easy, normal or hard"
+            )),
+        }
+    }
+}
+
+impl fmt::Display for Difficulty {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Difficulty::Easy => "easy",
+            Difficulty::Normal => "normal",
+            Difficulty::Hard => "hard",
+        };
+        write!(f, "{name}")
+    }
+}
+
+// what the game says after a wrong guess
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Feedback {
+    // too small or too big, like the book
+    Classic,
+    // hotter or colder than the guess before
+    HotCold,
+    // too small or too big, but wrong this often (0.0 to 1.0)
+    Liar(f64),
+}
+
+impl FromStr for Feedback {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "classic" => Ok(Feedback::Classic),
+            "hot-cold" | "hotcold" | "hot/cold" => Ok(Feedback::HotCold),
+            "liar" => Ok(Feedback::Liar(0.2)),
+            other => match other.strip_prefix("liar:").map(str::parse::<f64>) {
+                Some(Ok(p)) if (0.0..=1.0).contains(&p) => Ok(Feedback::Liar(p)),
+                Some(_) => Err(format!(
+                    "the liar's chance of lying has to be from 0 to 1, got \"{s}\""
+                )),
+                None => Err(format!(
+                    "unknown feedback \"{s}\", expected classic, hot-cold or liar[:chance]"
+                )),
+            },
+        }
+    }
+}
+
+impl fmt::Display for Feedback {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Feedback::Classic => write!(f, "classic"),
+            Feedback::HotCold => write!(f, "hot-cold"),
+            Feedback::Liar(p) => write!(f, "liar:{p}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Settings {
+    pub range: RangeInclusive<i32>,
+    // None to keep guessing until you get it
+    pub max_attempts: Option<u32>,
+    pub feedback: Feedback,
+}
+
+impl Settings {
+    pub fn new(range: RangeInclusive<i32>, max_attempts: Option<u32>) -> Result<Self, String> {
+        if range.is_empty() {
+            return Err(format!(
+                "the range {}..={} is empty",
+                range.start(),
+                range.end()
+            ));
+        }
+        if max_attempts == Some(0) {
+            return Err(String::from("there has to be at least one attempt"));
+        }
+        Ok(Settings {
+            range,
+            max_attempts,
+            feedback: Feedback::Classic,
+        })
+    }
+
+    pub fn with_feedback(self, feedback: Feedback) -> Self {
+        Settings { feedback, ..self }
+    }
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Difficulty::Normal.settings()
+    }
+}
+
+impl fmt::Display for Settings {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "a number from {} to {}",
+            self.range.start(),
+            self.range.end()
+        )?;
+        match self.max_attempts {
+            Some(1) => write!(f, ", 1 guess")?,
+            Some(n) => write!(f, ", {n} guesses")?,
+            None => write!(f, ", as many guesses as you like")?,
+        }
+        match self.feedback {
+            Feedback::Classic => Ok(()),
+            Feedback::HotCold => write!(f, ", hot or cold"),
+            Feedback::Liar(p) => write!(f, ", lying {:.0}% of the time", p * 100.0),
+        }
+    }
+}
+
+// "1..=500" or "1..500", the same as a range in rust
+pub fn parse_range(text: &str) -> Result<RangeInclusive<i32>, String> {
+    let bad = || format!("expected a range like 1..=100, got \"{text}\"");
+    let (low, high, inclusive) = match text.split_once("..=") {
+        Some((low, high)) => (low, high, true),
+        None => {
+            let (low, high) = text.split_once("..").ok_or_else(bad)?;
+            (low, high, false)
+        }
+    };
+    let low: i32 = low.trim().parse().map_err(|_| bad())?;
+    let high: i32 = high.trim().parse().map_err(|_| bad())?;
+    if inclusive {
+        Ok(low..=high)
+    } else {
+        // an exclusive range ending at i32::MIN would be empty anyway
+        Ok(low..=high.checked_sub(1).ok_or_else(bad)?)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum End {
+    Won,
+    OutOfAttempts,
+    // exit, quit or the end of the input
+    GaveUp,
+}
+
+// how a game went
+#[derive(Debug, Clone, PartialEq)]
+pub struct Summary {
+    pub secret: i32,
+    // the valid guesses in the order they were made, bad input doesn't count
+    pub guesses: Vec<i32>,
+    pub hints: u32,
+    pub end: End,
+    // from the start of the game to the last guess (or giving up)
+    pub duration: Duration,
+}
+
+impl Summary {
+    // guesses plus what the hints cost
+    pub fn attempts(&self) -> u32 {
+        self.guesses.len() as u32 + self.hints * HINT_COST
+    }
+}
+
+// the repl program, asks for a difficulty and then plays, the score goes in the default file
+pub fn guessing_game(input: &mut dyn BufRead, out: &mut dyn Report) {
+    // like progress, a score that can't be saved doesn't spoil the game
+    let scores = match scores::default_path() {
+        Ok(path) => Some(path),
+        Err(e) => {
+            say!(out, "scores won't be saved: {e}");
+            None
+        }
+    };
+    play_and_save(input, out, scores.as_deref(), &storage::current_user());
+}
+
+// the same with the scores file and player given, nothing is saved without a file
+pub fn play_and_save(
+    input: &mut dyn BufRead,
+    out: &mut dyn Report,
+    scores: Option<&Path>,
+    player: &str,
+) {
+    say!(
+        out,
+        "Pick a difficulty: easy, normal or hard, or a range like 1..=500 (normal if left empty)"
+    );
+    for difficulty in Difficulty::ALL {
+        say!(out, "  {difficulty:<8} {}", difficulty.settings());
+    }
+    say!(
+        out,
+        "Add hot-cold or liar (e.g. \"hard liar\" or \"1..=50 liar:0.1\") for different feedback."
+    );
+    let settings = loop {
+        let Some(line) = prompt_line(input, out, PROMPT) else {
+            return;
+        };
+        match choose(&line) {
+            Ok(settings) => break settings,
+            Err(e) => say!(out, "{e}"),
+        }
+    };
+    let summary = play(&settings, input, out);
+    if let Some(path) = scores {
+        if let Err(e) = scores::save(path, &settings, &summary, player) {
+            say!(out, "could not save the score: {e}");
+        }
+    }
+}
+
+// "hard", "1..=500", "normal hot-cold", ...
+fn choose(line: &str) -> Result<Settings, String> {
+    let mut words = line.split_whitespace();
+    let settings = match words.next() {
+        None => Settings::default(),
+        Some(range) if range.contains("..") => Settings::new(parse_range(range)?, None)?,
+        Some(difficulty) => difficulty.parse::<Difficulty>()?.settings(),
+    };
+    match (words.next(), words.next()) {
+        (None, _) => Ok(settings),
+        (Some(feedback), None) => Ok(settings.with_feedback(feedback.parse()?)),
+        (Some(_), Some(extra)) => Err(format!("unexpected \"{extra}\"")),
+    }
+}
+
+// what a single guess did
+#[derive(Debug, Clone, PartialEq)]
+pub enum Outcome {
+    // classic and liar feedback (the liar's might not be true)
+    TooSmall,
+    TooBig,
+    // hot/cold feedback compared to the previous guess
+    Warmer,
+    Colder,
+    SameDistance,
+    // hot/cold feedback for the first guess, how close it is compared to the size of the range
+    Hot,
+    Warm,
+    Cold,
+    Correct,
+    // outside the game's range, doesn't use up an attempt
+    OutOfRange(OutOfRange),
+    // the game already ended, the guess is ignored
+    GameOver,
+}
+
+// something true about the secret, bought with an attempt
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Hint {
+    Even(bool),
+    DivisibleBy(i32, bool),
+}
+
+impl fmt::Display for Hint {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Hint::Even(true) => write!(f, "the number is even"),
+            Hint::Even(false) => write!(f, "the number is odd"),
+            Hint::DivisibleBy(n, true) => write!(f, "the number is divisible by {n}"),
+            Hint::DivisibleBy(n, false) => write!(f, "the number is not divisible by {n}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum NoHint {
+    GameOver,
+    // a hint can't use up the last attempt
+    NotEnoughAttempts,
+    NoneLeft,
+}
+
+impl fmt::Display for NoHint {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            NoHint::GameOver => write!(f, "the game is over"),
+            NoHint::NotEnoughAttempts => write!(f, "not enough attempts left to pay for a hint"),
+            NoHint::NoneLeft => write!(f, "there are no hints left"),
+        }
+    }
+}
+
+// the game without any input or output, so it can be driven by a terminal, a test or a network
+// connection (see play below)
+//
+//   let mut game = GuessingGame::new(Settings::default(), &mut StdRng::seed_from_u64(1));
+//   game.guess(50) // Outcome::TooBig
+#[derive(Debug, Clone)]
+pub struct GuessingGame {
+    settings: Settings,
+    secret: i32,
+    guesses: Vec<i32>,
+    hints: u32,
+    gave_up: bool,
+    // for the liar's coin flips
+    rng: StdRng,
+    started: Instant,
+    ended: Option<Instant>,
+}
+
+impl GuessingGame {
+    // the secret (and any lies) come from rng, a seeded StdRng always plays the same game
+    pub fn new(settings: Settings, rng: &mut impl Rng) -> Self {
+        let secret = rng.gen_range(settings.range.clone());
+        let mut game = Self::with_secret(settings, secret);
+        game.rng = StdRng::seed_from_u64(rng.gen());
+        game
+    }
+
+    pub fn with_secret(settings: Settings, secret: i32) -> Self {
+        GuessingGame {
+            settings,
+            secret,
+            guesses: Vec::new(),
+            hints: 0,
+            gave_up: false,
+            rng: StdRng::seed_from_u64(0),
+            started: Instant::now(),
+            ended: None,
+        }
+    }
+
+    pub fn settings(&self) -> &Settings {
+        &self.settings
+    }
+
+    pub fn secret(&self) -> i32 {
+        self.secret
+    }
+
+    // the guesses that counted, oldest first
+    pub fn guesses(&self) -> &[i32] {
+        &self.guesses
+    }
+
+    // guesses plus what the hints cost
+    pub fn attempts(&self) -> u32 {
+        self.guesses.len() as u32 + self.hints * HINT_COST
+    }
+
+    // None when there's no limit
+    pub fn attempts_left(&self) -> Option<u32> {
+        self.settings
+            .max_attempts
+            .map(|max| max.saturating_sub(self.attempts()))
+    }
+
+    pub fn guess(&mut self, value: i32) -> Outcome {
+        if self.end().is_some() {
+            return Outcome::GameOver;
+        }
+        let (low, high) = (*self.settings.range.start(), *self.settings.range.end());
+        let guess = match Guess::between(value, low, high) {
+            Ok(guess) => guess,
+            Err(e) => return Outcome::OutOfRange(e),
+        };
+        let previous = self.guesses.last().copied();
+        self.guesses.push(guess.value());
+        if self.end().is_some() {
+            self.ended = Some(Instant::now());
+        }
+        if guess.value() == self.secret {
+            return Outcome::Correct;
+        }
+        match self.settings.feedback {
+            Feedback::Classic => self.compare(guess.value()),
+            Feedback::Liar(p) => match (self.compare(guess.value()), self.rng.gen_bool(p)) {
+                (Outcome::TooSmall, true) => Outcome::TooBig,
+                (Outcome::TooBig, true) => Outcome::TooSmall,
+                (outcome, _) => outcome,
+            },
+            Feedback::HotCold => self.temperature(guess.value(), previous),
+        }
+    }
+
+    fn compare(&self, guess: i32) -> Outcome {
+        if guess < self.secret {
+            Outcome::TooSmall
+        } else {
+            Outcome::TooBig
+        }
+    }
+
+    fn temperature(&self, guess: i32, previous: Option<i32>) -> Outcome {
+        let distance = |n: i32| (i64::from(n) - i64::from(self.secret)).abs();
+        match previous {
+            Some(previous) => match distance(guess).cmp(&distance(previous)) {
+                Ordering::Less => Outcome::Warmer,
+                Ordering::Greater => Outcome::Colder,
+                Ordering::Equal => Outcome::SameDistance,
+            },
+            None => {
+                let size = i64::from(*self.settings.range.end())
+                    - i64::from(*self.settings.range.start())
+                    + 1;
+                // within a tenth of the range is hot, within a quarter is warm
+                match distance(guess) * 20 / size {
+                    0..=1 => Outcome::Hot,
+                    2..=4 => Outcome::Warm,
+                    _ => Outcome::Cold,
+                }
+            }
+        }
+    }
+
+    // even or odd first, then divisibility, each one costs HINT_COST attempts
+    pub fn hint(&mut self) -> Result<Hint, NoHint> {
+        if self.end().is_some() {
+            return Err(NoHint::GameOver);
+        }
+        if self.attempts_left().is_some_and(|left| left <= HINT_COST) {
+            return Err(NoHint::NotEnoughAttempts);
+        }
+        let hint = match self.hints {
+            0 => Hint::Even(self.secret % 2 == 0),
+            n => {
+                let divisor = *DIVISORS.get(n as usize - 1).ok_or(NoHint::NoneLeft)?;
+                Hint::DivisibleBy(divisor, self.secret % divisor == 0)
+            }
+        };
+        self.hints += 1;
+        Ok(hint)
+    }
+
+    pub fn give_up(&mut self) {
+        if self.end().is_none() {
+            self.gave_up = true;
+            self.ended = Some(Instant::now());
+        }
+    }
+
+    // None while the game is still going
+    pub fn end(&self) -> Option<End> {
+        if self.guesses.last() == Some(&self.secret) {
+            Some(End::Won)
+        } else if self.gave_up {
+            Some(End::GaveUp)
+        } else if self.attempts_left() == Some(0) {
+            Some(End::OutOfAttempts)
+        } else {
+            None
+        }
+    }
+
+    // how the game went so far, a game that hasn't ended counts as given up
+    pub fn summary(&self) -> Summary {
+        Summary {
+            secret: self.secret,
+            guesses: self.guesses.clone(),
+            hints: self.hints,
+            end: self.end().unwrap_or(End::GaveUp),
+            duration: self.ended.unwrap_or_else(Instant::now) - self.started,
+        }
+    }
+}
+
+// a new game with a random secret, played on input
+pub fn play(settings: &Settings, input: &mut dyn BufRead, out: &mut dyn Report) -> Summary {
+    let mut game = GuessingGame::new(settings.clone(), &mut rand::thread_rng());
+    drive(&mut game, input, out)
+}
+
+// reads guesses from input until the game ends, then prints the summary
+pub fn drive(game: &mut GuessingGame, input: &mut dyn BufRead, out: &mut dyn Report) -> Summary {
+    say!(out, "Guess the number! It's {}.", game.settings());
+    say!(
+        out,
+        "Type \"hint\" for a clue about the number, it costs {HINT_COST} attempt."
+    );
+    while game.end().is_none() {
+        match game.attempts_left() {
+            Some(1) => say!(out, "Please input your guess (last one!)."),
+            Some(n) => say!(out, "Please input your guess ({n} left)."),
+            None => say!(out, "Please input your guess."),
+        }
+        let Some(line) = prompt_line(input, out, PROMPT) else {
+            game.give_up();
+            break;
+        };
+        if line == "hint" {
+            match game.hint() {
+                Ok(hint) => say!(out, "Hint: {hint}."),
+                Err(e) => say!(out, "No hint, {e}."),
+            }
+            continue;
+        }
+        // bad input is pointed out and doesn't use up an attempt
+        let value: i32 = match line.parse() {
+            Ok(value) => value,
+            Err(_) => {
+                say!(out, "\"{line}\" is not a number, try again.");
+                continue;
+            }
+        };
+        match game.guess(value) {
+            Outcome::TooSmall => say!(out, "Too small."),
+            Outcome::TooBig => say!(out, "Too big."),
+            Outcome::Warmer => say!(out, "Warmer."),
+            Outcome::Colder => say!(out, "Colder."),
+            Outcome::SameDistance => say!(out, "No warmer, no colder."),
+            Outcome::Hot => say!(out, "Hot!"),
+            Outcome::Warm => say!(out, "Warm."),
+            Outcome::Cold => say!(out, "Cold."),
+            Outcome::OutOfRange(e) => say!(out, "{e}, try again."),
+            Outcome::Correct | Outcome::GameOver => {}
+        }
+    }
+
+    let summary = game.summary();
+    print_summary(&summary, out);
+    summary
+}
+
+fn print_summary(summary: &Summary, out: &mut dyn Report) {
+    let tries = match summary.guesses.len() {
+        1 => String::from("1 guess"),
+        n => format!("{n} guesses"),
+    };
+    let hints = match summary.hints {
+        0 => String::new(),
+        1 => String::from(" and 1 hint"),
+        n => format!(" and {n} hints"),
+    };
+    match summary.end {
+        End::Won => say!(
+            out,
+            "You win! You got {} in {tries}{hints}.",
+            summary.secret
+        ),
+        End::OutOfAttempts => say!(out, "Out of guesses! The number was {}.", summary.secret),
+        End::GaveUp => say!(out, "The number was {}.", summary.secret),
+    }
+    if !summary.guesses.is_empty() {
+        let guesses: Vec<String> = summary.guesses.iter().map(i32::to_string).collect();
+        say!(out, "Your {tries}: {}", guesses.join(", "));
+    }
+}
@@ -0,0 +1,76 @@
+//! Notes and exercises from working through The Rust Programming Language, one module per chapter.
+//!
+//! The homework solutions are also usable as a small library:
+//!
+//! ```
+//! use rust_book::{f_to_c, fib_n, string_to_pig_latin, Data, Guess};
+//!
+//! assert_eq!(f_to_c(212.0), 100.0);
+//! assert_eq!(fib_n(6), vec![0, 1, 1, 2, 3, 5]);
+//! assert_eq!(string_to_pig_latin("first apple"), "irst-fay apple-hay");
+//! assert_eq!(Data::new(&mut [3, 1, 2, 2]).mode, 2);
+//! assert_eq!(Guess::new(42).value(), 42);
+//! ```
+//!
+//! The `rust_book` binary is a front end over this crate for running the chapters.
+pub mod bigint;
+pub mod chapter;
+// the chapter modules are notes that go the long way round on purpose, so clippy is kept quiet there
+// and the examples that are only there to be read don't count as unused
+#[allow(unused)]
+#[allow(clippy::all)]
+pub mod chapter_eight;
+#[allow(unused)]
+#[allow(clippy::all)]
+pub mod chapter_eight_hw;
+#[allow(unused)]
+#[allow(clippy::all)]
+pub mod chapter_five;
+#[allow(unused)]
+#[allow(clippy::all)]
+pub mod chapter_four;
+#[allow(unused)]
+#[allow(clippy::all)]
+pub mod chapter_nine;
+#[allow(unused)]
+#[allow(clippy::all)]
+pub mod chapter_one;
+#[allow(unused)]
+#[allow(clippy::all)]
+pub mod chapter_seven;
+#[allow(unused)]
+#[allow(clippy::all)]
+pub mod chapter_six;
+#[allow(unused)]
+#[allow(clippy::all)]
+pub mod chapter_three;
+#[allow(unused)]
+#[allow(clippy::all)]
+pub mod chapter_three_hw;
+#[allow(unused)]
+#[allow(clippy::all)]
+pub mod chapter_two;
+pub mod expr;
+pub mod fibonacci;
+pub mod guessing_game;
+pub mod literal;
+pub mod multiplayer;
+pub mod numbers;
+pub mod output;
+pub mod overflow;
+pub mod progress;
+pub mod quiz;
+pub mod repl;
+pub mod scores;
+pub mod snippets;
+pub mod solver;
+pub mod song;
+pub mod storage;
+pub mod table;
+pub mod temperature;
+pub mod units;
+pub mod verify;
+
+pub use chapter_eight_hw::{string_to_pig_latin, Data};
+pub use chapter_nine::Guess;
+pub use chapter_three_hw::{f_to_c, fib_n};
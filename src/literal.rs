@@ -0,0 +1,445 @@
+// number literals the way rustc reads them, every form from the notes on ints():
+//
+//   98_222        underscores anywhere after the first digit
+//   0xff 0o77     hexadecimal and octal
+//   0b1111_0000   binary
+//   b'A'          a byte, always a u8 (escapes like b'\n' and b'\x7f' too)
+//   57u8 2.5f32   a suffix gives the type, without one it's i32 (f64 with a point or exponent)
+//   6.02e23       an exponent makes it a float
+//   -128i8        a - in front negates it, so it fits where 128i8 wouldn't (and -1u8 is an error)
+//
+// and the out of range ones as errors with rustc's explanation, 256u8 won't compile (the
+// overflowing_literals lint is deny by default) and neither will 1e40f32
+//
+//   "0xffi8".parse::<Literal>()?.value()
+//   literal out of range for `i8`: the literal `0xffi8` (decimal `255`) does not fit into the type
+//   `i8` and will become `-1i8`
+
+use std::{fmt, iter::Peekable, str::Chars, str::FromStr};
+
+use crate::expr::{Ty, Type, Value, Wide};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Base {
+    Binary,
+    Octal,
+    Decimal,
+    Hexadecimal,
+}
+
+impl Base {
+    pub fn radix(&self) -> u32 {
+        match self {
+            Base::Binary => 2,
+            Base::Octal => 8,
+            Base::Decimal => 10,
+            Base::Hexadecimal => 16,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Base::Binary => "binary",
+            Base::Octal => "octal",
+            Base::Decimal => "decimal",
+            Base::Hexadecimal => "hexadecimal",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Kind {
+    Integer(u128, Base),
+    // the digits without underscores or suffix, parsed once the type is known so an f32 is
+    // rounded straight from the text rather than by way of an f64
+    Float(String),
+    Byte(u8),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Literal {
+    // as it was written, without the - in front of a negated one
+    pub text: String,
+    pub kind: Kind,
+    pub suffix: Option<Type>,
+    // -128i8 given to parse, rust reads the - as negating the literal rather than part of it
+    pub negated: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LiteralError {
+    // the text doesn't start with a literal, or has more after it
+    NotALiteral(String),
+    InvalidDigit {
+        digit: char,
+        base: Base,
+    },
+    NoDigits,
+    MissingExponent,
+    // more than a u128 can hold, which rustc won't even lex
+    TooLarge,
+    InvalidSuffix {
+        suffix: String,
+        float: bool,
+    },
+    // 0x1.8 and 0b1f32, only decimal floats exist
+    UnsupportedFloat(Base),
+    Byte(String),
+    // a - in front of an unsigned one, -1u8 is an error even though -0u8 would fit
+    Negate(Type),
+    // doesn't fit its type, with rustc's note on what it would have been
+    OutOfRange {
+        literal: String,
+        ty: Type,
+        note: String,
+    },
+}
+
+impl fmt::Display for LiteralError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            LiteralError::NotALiteral(text) => write!(f, "\"{text}\" isn't a number literal"),
+            LiteralError::InvalidDigit { digit, base } => {
+                write!(
+                    f,
+                    "invalid digit `{digit}` for a base {} literal",
+                    base.radix()
+                )
+            }
+            LiteralError::NoDigits => write!(f, "no valid digits found for number"),
+            LiteralError::MissingExponent => write!(f, "expected at least one digit in exponent"),
+            LiteralError::TooLarge => write!(f, "integer literal is too large"),
+            LiteralError::InvalidSuffix { suffix, float } => write!(
+                f,
+                "invalid suffix `{suffix}` for {} literal",
+                if *float { "float" } else { "number" }
+            ),
+            LiteralError::UnsupportedFloat(base) => {
+                write!(f, "{} float literal is not supported", base.name())
+            }
+            LiteralError::Byte(message) => write!(f, "{message}"),
+            LiteralError::Negate(ty) => {
+                write!(f, "cannot apply unary operator `-` to type `{ty}`")
+            }
+            LiteralError::OutOfRange { ty, note, .. } => {
+                write!(f, "literal out of range for `{ty}`: {note}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for LiteralError {}
+
+impl Literal {
+    // reads one literal off the front of chars, leaving whatever follows it
+    pub fn lex(chars: &mut Peekable<Chars>) -> Result<Literal, LiteralError> {
+        let mut text = String::new();
+        match chars.peek() {
+            Some('b') => return byte(chars),
+            Some(c) if c.is_ascii_digit() => {}
+            _ => return Err(LiteralError::NotALiteral(chars.collect())),
+        }
+
+        let mut ahead = chars.clone();
+        ahead.next();
+        let base = match (chars.peek(), ahead.peek()) {
+            (Some('0'), Some('x')) => Base::Hexadecimal,
+            (Some('0'), Some('o')) => Base::Octal,
+            (Some('0'), Some('b')) => Base::Binary,
+            _ => Base::Decimal,
+        };
+        if base != Base::Decimal {
+            text.extend(chars.take(2));
+        }
+        let digits = match base {
+            Base::Hexadecimal => take(chars, &mut text, |c| c.is_ascii_hexdigit() || c == '_'),
+            // 0b102 is a mistake in a binary literal, not a binary literal with a suffix of 2
+            _ => take(chars, &mut text, |c| c.is_ascii_digit() || c == '_'),
+        };
+        if let Some(digit) = digits
+            .chars()
+            .find(|c| *c != '_' && c.to_digit(base.radix()).is_none())
+        {
+            return Err(LiteralError::InvalidDigit { digit, base });
+        }
+
+        let mut float = false;
+        // 1.5 and 1. but not 1..2 or 1.max(2)
+        let mut ahead = chars.clone();
+        if ahead.next() == Some('.')
+            && !ahead
+                .peek()
+                .is_some_and(|c| *c == '.' || *c == '_' || c.is_alphabetic())
+        {
+            if base != Base::Decimal {
+                return Err(LiteralError::UnsupportedFloat(base));
+            }
+            text.extend(chars.next());
+            take(chars, &mut text, |c| c.is_ascii_digit() || c == '_');
+            float = true;
+        }
+        // an e after decimal digits always starts an exponent, so 1e, 1em and 1e_ are all missing
+        // its digits rather than having a suffix
+        if base == Base::Decimal && matches!(chars.peek(), Some('e' | 'E')) {
+            text.extend(chars.next());
+            text.extend(chars.next_if(|c| *c == '+' || *c == '-'));
+            let exponent = take(chars, &mut text, |c| c.is_ascii_digit() || c == '_');
+            if !exponent.chars().any(|c| c.is_ascii_digit()) {
+                return Err(LiteralError::MissingExponent);
+            }
+            float = true;
+        }
+
+        // 0x on its own, or 0xg, is missing its digits before it has a suffix
+        let digits: String = digits.chars().filter(|c| *c != '_').collect();
+        if digits.is_empty() {
+            return Err(LiteralError::NoDigits);
+        }
+
+        let written = take(chars, &mut text, |c| c.is_alphanumeric() || c == '_');
+        let suffix = match written.as_str() {
+            "" => None,
+            written => Some(
+                written
+                    .parse::<Type>()
+                    .map_err(|_| LiteralError::InvalidSuffix {
+                        suffix: written.to_string(),
+                        float,
+                    })?,
+            ),
+        };
+        let kind = match suffix {
+            Some(ty) if ty.is_float() && base != Base::Decimal => {
+                return Err(LiteralError::UnsupportedFloat(base))
+            }
+            Some(ty) if float && !ty.is_float() => {
+                return Err(LiteralError::InvalidSuffix {
+                    suffix: written,
+                    float,
+                })
+            }
+            Some(ty) if ty.is_float() => Kind::Float(float_digits(&text, &written)),
+            _ if float => Kind::Float(float_digits(&text, &written)),
+            _ => Kind::Integer(
+                u128::from_str_radix(&digits, base.radix()).map_err(|_| LiteralError::TooLarge)?,
+                base,
+            ),
+        };
+        Ok(Literal {
+            text,
+            kind,
+            suffix,
+            negated: false,
+        })
+    }
+
+    // {integer} and {float} until something says otherwise
+    pub fn ty(&self) -> Ty {
+        match (&self.kind, self.suffix) {
+            (Kind::Byte(_), _) => Ty::Known(Type::U8),
+            (_, Some(ty)) => Ty::Known(ty),
+            (Kind::Integer(..), None) => Ty::Integer,
+            (Kind::Float(_), None) => Ty::Float,
+        }
+    }
+
+    // the value with rust's defaults for a literal without a suffix
+    pub fn value(&self) -> Result<Value, LiteralError> {
+        self.value_as(self.ty().or_default(), self.negated)
+    }
+
+    // the value as `ty`, negated for -128i8 which fits even though 128i8 doesn't. ty has to be
+    // one the literal can be (a float for a float literal, u8 for a byte)
+    pub fn value_as(&self, ty: Type, negated: bool) -> Result<Value, LiteralError> {
+        if negated && ty.is_unsigned() {
+            return Err(LiteralError::Negate(ty));
+        }
+        let sign = if negated { "-" } else { "" };
+        match &self.kind {
+            Kind::Byte(byte) => Ok(Value::U8(*byte)),
+            Kind::Integer(n, base) => {
+                let wide = match negated {
+                    true => 0i128.checked_sub_unsigned(*n).map(Wide::Signed),
+                    false => Some(Wide::Unsigned(*n)),
+                };
+                wide.and_then(|n| Value::fit(n, ty))
+                    .ok_or_else(|| LiteralError::OutOfRange {
+                        literal: format!("{sign}{}", self.text),
+                        ty,
+                        note: match base {
+                            Base::Decimal => format!(
+                                "the literal `{sign}{}` does not fit into the type `{ty}` whose range is `{}`",
+                                self.text,
+                                range(ty)
+                            ),
+                            _ => {
+                                let wrapped = Value::U128(*n).cast(ty);
+                                let wrapped = if negated { wrapping_neg(wrapped) } else { wrapped };
+                                format!(
+                                    "the literal `{sign}{}` (decimal `{sign}{n}`) does not fit into the type `{ty}` and will become `{wrapped}{ty}`",
+                                    self.text
+                                )
+                            }
+                        },
+                    })
+            }
+            Kind::Float(digits) => {
+                let value = match ty {
+                    Type::F32 => Value::F32(digits.parse().expect("the lexer checked the digits")),
+                    _ => Value::F64(digits.parse().expect("the lexer checked the digits")),
+                };
+                let infinite = match value {
+                    Value::F32(n) => n.is_infinite(),
+                    Value::F64(n) => n.is_infinite(),
+                    _ => false,
+                };
+                if infinite {
+                    return Err(LiteralError::OutOfRange {
+                        literal: format!("{sign}{}", self.text),
+                        ty,
+                        note: format!(
+                            "the literal `{sign}{}` does not fit into the type `{ty}` and will be converted to `{ty}::INFINITY`",
+                            self.text
+                        ),
+                    });
+                }
+                Ok(match (value, negated) {
+                    (Value::F32(n), true) => Value::F32(-n),
+                    (Value::F64(n), true) => Value::F64(-n),
+                    (value, _) => value,
+                })
+            }
+        }
+    }
+}
+
+// exactly one literal, a leading - is taken as negating it like -128i8
+impl FromStr for Literal {
+    type Err = LiteralError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let (negated, text) = match s.strip_prefix('-') {
+            Some(text) => (true, text),
+            None => (false, s),
+        };
+        let mut chars = text.chars().peekable();
+        let literal = Literal::lex(&mut chars)?;
+        match chars.peek() {
+            None => Ok(Literal { negated, ..literal }),
+            Some(_) => Err(LiteralError::NotALiteral(s.to_string())),
+        }
+    }
+}
+
+impl fmt::Display for Literal {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let sign = if self.negated { "-" } else { "" };
+        write!(f, "{sign}{}", self.text)
+    }
+}
+
+// moves the characters that keep matching from chars onto text, and returns them too
+fn take(chars: &mut Peekable<Chars>, text: &mut String, keep: impl Fn(char) -> bool) -> String {
+    let mut taken = String::new();
+    while let Some(c) = chars.next_if(|c| keep(*c)) {
+        taken.push(c);
+    }
+    text.push_str(&taken);
+    taken
+}
+
+// 6.02e23_f64 to 6.02e23
+fn float_digits(text: &str, suffix: &str) -> String {
+    text.strip_suffix(suffix)
+        .unwrap_or(text)
+        .chars()
+        .filter(|c| *c != '_')
+        .collect()
+}
+
+// b'A', b'\n', b'\x7f'
+fn byte(chars: &mut Peekable<Chars>) -> Result<Literal, LiteralError> {
+    let error = |message: &str| Err(LiteralError::Byte(message.to_string()));
+    let mut text = String::new();
+    text.extend(chars.next());
+    if chars.peek() != Some(&'\'') {
+        return Err(LiteralError::NotALiteral(text + &chars.collect::<String>()));
+    }
+    text.extend(chars.next());
+    let byte = match chars.next() {
+        None => return error("unterminated byte constant"),
+        Some('\'') => return error("empty byte literal"),
+        Some('\\') => {
+            text.push('\\');
+            let escape = chars.next();
+            text.extend(escape);
+            match escape {
+                Some('n') => b'\n',
+                Some('r') => b'\r',
+                Some('t') => b'\t',
+                Some('0') => b'\0',
+                Some('\\') => b'\\',
+                Some('\'') => b'\'',
+                Some('"') => b'"',
+                Some('x') => {
+                    let hex: String = chars.take(2).collect();
+                    text.push_str(&hex);
+                    match u8::from_str_radix(&hex, 16) {
+                        Ok(byte) if hex.len() == 2 => byte,
+                        _ => return error("numeric character escape is too short or not hex"),
+                    }
+                }
+                Some(c) => return Err(LiteralError::Byte(format!("unknown byte escape: `{c}`"))),
+                None => return error("unterminated byte constant"),
+            }
+        }
+        Some(c) if c.is_ascii() => {
+            text.push(c);
+            c as u8
+        }
+        Some(_) => return error("non-ASCII character in byte literal"),
+    };
+    match chars.next() {
+        Some('\'') => text.push('\''),
+        Some(_) => return error("byte literal may only contain one byte"),
+        None => return error("unterminated byte constant"),
+    }
+    if chars
+        .peek()
+        .is_some_and(|c| c.is_alphanumeric() || *c == '_')
+    {
+        return error("suffixes on byte literals are invalid");
+    }
+    Ok(Literal {
+        text,
+        kind: Kind::Byte(byte),
+        suffix: None,
+        negated: false,
+    })
+}
+
+// the range rustc quotes, `0..=255`
+fn range(ty: Type) -> String {
+    macro_rules! range {
+        ($($ty:ident $t:ty),+) => {
+            match ty {
+                $(Type::$ty => format!("{}..={}", <$t>::MIN, <$t>::MAX),)+
+                _ => String::new(),
+            }
+        };
+    }
+    range!(I8 i8, I16 i16, I32 i32, I64 i64, I128 i128, Isize isize, U8 u8, U16 u16, U32 u32,
+        U64 u64, U128 u128, Usize usize)
+}
+
+fn wrapping_neg(value: Value) -> Value {
+    match value {
+        Value::I8(n) => Value::I8(n.wrapping_neg()),
+        Value::I16(n) => Value::I16(n.wrapping_neg()),
+        Value::I32(n) => Value::I32(n.wrapping_neg()),
+        Value::I64(n) => Value::I64(n.wrapping_neg()),
+        Value::I128(n) => Value::I128(n.wrapping_neg()),
+        Value::Isize(n) => Value::Isize(n.wrapping_neg()),
+        value => value,
+    }
+}
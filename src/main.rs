@@ -1,39 +1,359 @@
-#![allow(unused)]
-mod chapter_eight;
-mod chapter_eight_hw;
-mod chapter_five;
-mod chapter_four;
-mod chapter_nine;
-mod chapter_one;
-mod chapter_seven;
-mod chapter_six;
-mod chapter_three;
-mod chapter_three_hw;
-mod chapter_two;
+mod cli;
+mod golden;
 
-fn main() {
-    println!("Lets learn Rust!");
-    // println!("Chapter 1");
-    // chapter_one::chapter_one();
-    // println!("Chapter 2");
-    // chapter_two::chapter_two();
-    // println!("Chapter 3");
-    // chapter_three::chapter_three();
-    // println!("Chapter 3 HW");
-    // let c: f32 = chapter_three_hw::f_to_c(112.0);
-    // let fibs: Vec<i32> = chapter_three_hw::fib_n(10);
-    // chapter_three_hw::twelve_days_of_xmas();
-    // println!("Chapter 4");
-    // chapter_four::chapter_four();
-    // println!("Chapter 5");
-    // chapter_five::chapter_five();
-    // println!("Chapter 6");
-    // chapter_six::chapter_six();
-    // println!("Chapter 7");
-    // chapter_seven::chapter_seven();
-    // println!("Chapter 8");
-    // chapter_eight::chapter_eight();
-    // println!("Chapter 8 HW");
-    // chapter_eight_hw::chapter_eight_hw();
-    chapter_nine::chapter_nine();
+use std::{
+    env, fs,
+    io::{self, BufRead, Write},
+    process::ExitCode,
+    thread,
+};
+
+use cli::{Command, RunOptions};
+use rust_book::multiplayer::{Client, Message, Request, Server};
+use rust_book::output::{self, Capture, Stdout};
+use rust_book::repl::Repl;
+use rust_book::{
+    fibonacci, guessing_game, overflow, progress, quiz, scores, snippets, solver, song, storage,
+    table, units, verify,
+};
+
+fn main() -> ExitCode {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let command = match cli::parse(&args) {
+        Ok(command) => command,
+        Err(e) => {
+            eprintln!("{e}\n\n{}", cli::USAGE);
+            return ExitCode::from(2);
+        }
+    };
+
+    match command {
+        Command::Help => {
+            println!("Lets learn Rust!\n\n{}", cli::USAGE);
+            ExitCode::SUCCESS
+        }
+        Command::List => {
+            cli::list();
+            ExitCode::SUCCESS
+        }
+        Command::Play(settings, player) => {
+            let summary = guessing_game::play(&settings, &mut io::stdin().lock(), &mut Stdout);
+            // like progress, a score that can't be saved doesn't spoil the game
+            let saved = scores::default_path()
+                .and_then(|path| scores::save(&path, &settings, &summary, &player));
+            if let Err(e) = saved {
+                eprintln!("could not save the score: {e}");
+            }
+            ExitCode::SUCCESS
+        }
+        Command::Leaderboard(difficulty) => {
+            match scores::default_path().and_then(|path| scores::load(&path)) {
+                Ok(file) => {
+                    scores::print(&file, difficulty, &mut Stdout);
+                    ExitCode::SUCCESS
+                }
+                Err(e) => {
+                    eprintln!("could not read the scores file: {e}");
+                    ExitCode::FAILURE
+                }
+            }
+        }
+        Command::Solve(options) => {
+            let strategies = match &options.strategy {
+                Some(name) => solver::strategy(name).into_iter().collect(),
+                None => solver::strategies(),
+            };
+            let stats: Vec<solver::Stats> = strategies
+                .into_iter()
+                .map(|mut strategy| {
+                    solver::compare(
+                        strategy.as_mut(),
+                        options.range.clone(),
+                        options.games,
+                        options.seed,
+                    )
+                })
+                .collect();
+            solver::report(&stats, &options.range, &mut Stdout);
+            ExitCode::SUCCESS
+        }
+        Command::Serve(settings, port) => {
+            let players = settings.players;
+            let server = match Server::bind(("127.0.0.1", port), settings, &mut rand::thread_rng())
+            {
+                Ok(server) => server,
+                Err(e) => {
+                    eprintln!("could not listen on port {port}: {e}");
+                    return ExitCode::FAILURE;
+                }
+            };
+            println!(
+                "waiting for {players} players, join with `rust_book join <name> --port {port}`"
+            );
+            match server.run() {
+                Ok(Some(winner)) => println!("{winner} won"),
+                Ok(None) => println!("everyone left before the number was found"),
+                Err(e) => {
+                    eprintln!("the game stopped: {e}");
+                    return ExitCode::FAILURE;
+                }
+            }
+            ExitCode::SUCCESS
+        }
+        Command::Join(name, port) => {
+            let (mut client, mut writer) = match Client::connect(("127.0.0.1", port), &name)
+                .and_then(|client| {
+                    let writer = client.stream().try_clone()?;
+                    Ok((client, writer))
+                }) {
+                Ok(connection) => connection,
+                Err(e) => {
+                    eprintln!("could not join the game on port {port}: {e}");
+                    return ExitCode::FAILURE;
+                }
+            };
+            match choose_name(&mut client, name) {
+                Ok(true) => {}
+                Ok(false) => {
+                    println!("the server closed the game");
+                    return ExitCode::SUCCESS;
+                }
+                Err(e) => {
+                    eprintln!("lost the connection: {e}");
+                    return ExitCode::FAILURE;
+                }
+            }
+            // guesses are typed while the server's messages keep arriving, so stdin gets a thread
+            // of its own. it is left blocked on stdin when the game ends, returning from main ends it
+            thread::spawn(move || {
+                for line in io::stdin().lock().lines() {
+                    let Ok(line) = line else { break };
+                    match line.trim().parse() {
+                        Ok(n) => {
+                            if writeln!(writer, "{}", Request::Guess(n)).is_err() {
+                                break;
+                            }
+                        }
+                        Err(_) => println!("Please type a number!"),
+                    }
+                }
+            });
+            loop {
+                match client.receive() {
+                    Ok(Some(message)) => {
+                        println!("{}", message.describe());
+                        if let Message::Winner { .. } = message {
+                            return ExitCode::SUCCESS;
+                        }
+                    }
+                    Ok(None) => {
+                        println!("the server closed the game");
+                        return ExitCode::SUCCESS;
+                    }
+                    Err(e) => {
+                        eprintln!("lost the connection: {e}");
+                        return ExitCode::FAILURE;
+                    }
+                }
+            }
+        }
+        Command::Table(options) => {
+            let table = match table::conversions(options.from, &options.to, &options.steps) {
+                Ok(table) => table,
+                Err(e) => {
+                    eprintln!("{e}");
+                    return ExitCode::FAILURE;
+                }
+            };
+            let text = table.render(options.style, options.precision);
+            match &options.output {
+                Some(path) => {
+                    if let Err(e) = fs::write(path, text) {
+                        eprintln!("could not write {}: {e}", path.display());
+                        return ExitCode::FAILURE;
+                    }
+                }
+                None => print!("{text}"),
+            }
+            ExitCode::SUCCESS
+        }
+        Command::Convert(expression) => match units::Conversion::parse(&expression)
+            .and_then(|conversion| Ok((conversion.result()?, conversion)))
+        {
+            Ok((result, conversion)) => {
+                println!("{conversion} = {result}");
+                ExitCode::SUCCESS
+            }
+            Err(e) => {
+                eprintln!("{e}");
+                ExitCode::FAILURE
+            }
+        },
+        Command::Overflow(expression) => {
+            overflow::report(&expression, &mut Stdout);
+            ExitCode::SUCCESS
+        }
+        Command::Fib(n, modulus) => {
+            let result = match modulus {
+                Some(m) => fibonacci::fib_mod(&n, m).map(|f| format!("F({n}) mod {m} = {f}")),
+                // fast doubling still needs n's bits in a u64
+                None => match u128::try_from(&n).ok().and_then(|n| u64::try_from(n).ok()) {
+                    Some(n) => Ok(format!("F({n}) = {}", fibonacci::nth_fib(n))),
+                    None => {
+                        eprintln!("F({n}) has too many digits to print, try it with --mod");
+                        return ExitCode::FAILURE;
+                    }
+                },
+            };
+            match result {
+                Ok(line) => {
+                    println!("{line}");
+                    ExitCode::SUCCESS
+                }
+                Err(e) => {
+                    eprintln!("{e}");
+                    ExitCode::FAILURE
+                }
+            }
+        }
+        Command::Sequence(recurrence, count) => {
+            println!("{recurrence}");
+            let terms: Vec<String> = recurrence
+                .terms()
+                .take(count)
+                .map(|a| a.to_string())
+                .collect();
+            println!("{}", terms.join(", "));
+            if terms.len() < count {
+                println!("(the next term doesn't fit in an i128)");
+            }
+            ExitCode::SUCCESS
+        }
+        Command::Sing(name, style, output) => {
+            let song = match song::builtin(&name) {
+                Some(song) => Ok(song),
+                None => fs::read_to_string(&name)
+                    .map_err(|e| format!("no song called \"{name}\" and no file either: {e}"))
+                    .and_then(|text| text.parse().map_err(|e| format!("{name}: {e}"))),
+            };
+            let text = match song {
+                Ok(song) => song.render(style),
+                Err(e) => {
+                    eprintln!("{e}");
+                    return ExitCode::FAILURE;
+                }
+            };
+            match &output {
+                Some(path) => {
+                    if let Err(e) = fs::write(path, text) {
+                        eprintln!("could not write {}: {e}", path.display());
+                        return ExitCode::FAILURE;
+                    }
+                }
+                None => print!("{text}"),
+            }
+            ExitCode::SUCCESS
+        }
+        Command::Quiz(chapter) => {
+            quiz::play(chapter.as_deref(), &mut io::stdin().lock(), &mut Stdout);
+            ExitCode::SUCCESS
+        }
+        Command::Verify(chapter) => {
+            let checks = match verify::check_all(&snippets::from_chapter(chapter.as_deref())) {
+                Ok(checks) => checks,
+                Err(e) => {
+                    eprintln!("could not set up a directory to compile in: {e}");
+                    return ExitCode::FAILURE;
+                }
+            };
+            if verify::report(&checks, &mut Stdout) {
+                ExitCode::SUCCESS
+            } else {
+                ExitCode::FAILURE
+            }
+        }
+        Command::Repl => {
+            Repl::new().run(&mut io::stdin().lock(), &mut Stdout);
+            ExitCode::SUCCESS
+        }
+        Command::Bless(dir) => match golden::bless(&dir) {
+            Ok(written) => {
+                for path in written {
+                    println!("wrote {}", path.display());
+                }
+                ExitCode::SUCCESS
+            }
+            Err(e) => {
+                eprintln!("could not write the snapshots: {e}");
+                ExitCode::FAILURE
+            }
+        },
+        Command::Progress => {
+            let entries = match progress::default_path().and_then(|path| progress::load(&path)) {
+                Ok(entries) => entries,
+                Err(e) => {
+                    eprintln!("could not read the progress file: {e}");
+                    return ExitCode::FAILURE;
+                }
+            };
+            progress::summary(&entries, &storage::current_user(), &mut Stdout);
+            ExitCode::SUCCESS
+        }
+        // exits with a non zero value when a chapter panics (see the notes on main in chapter nine)
+        Command::Run(target, options) => {
+            let entries = if options.is_streamed() {
+                cli::run(&target, &mut Stdout)
+            } else {
+                let mut capture = Capture::new();
+                let entries = cli::run(&target, &mut capture);
+                if let Err(e) = write_capture(&capture, &options) {
+                    eprintln!("could not write the output: {e}");
+                    return ExitCode::FAILURE;
+                }
+                entries
+            };
+            // not being able to save progress shouldn't fail the run itself
+            if let Err(e) =
+                progress::default_path().and_then(|path| progress::record(&path, &entries))
+            {
+                eprintln!("could not save progress: {e}");
+            }
+            if entries.iter().all(|entry| entry.passed) {
+                ExitCode::SUCCESS
+            } else {
+                ExitCode::FAILURE
+            }
+        }
+    }
+}
+
+// waits for the server to accept the name, asking for another while it's taken (or not one word).
+// false if the server or stdin closed first
+fn choose_name(client: &mut Client, mut name: String) -> io::Result<bool> {
+    loop {
+        match client.receive()? {
+            Some(Message::Joined(joined)) if joined == name => {
+                println!("{}", Message::Joined(joined).describe());
+                return Ok(true);
+            }
+            Some(Message::Error(e)) => {
+                println!("{e}, pick another name:");
+                let mut line = String::new();
+                if io::stdin().read_line(&mut line)? == 0 {
+                    return Ok(false);
+                }
+                name = line.trim().to_string();
+                client.send(&Request::Name(name.clone()))?;
+            }
+            Some(message) => println!("{}", message.describe()),
+            None => return Ok(false),
+        }
+    }
+}
+
+fn write_capture(capture: &Capture, options: &RunOptions) -> io::Result<()> {
+    match &options.output {
+        Some(path) => output::write_file(path, capture, options.format),
+        None => output::write_to(&mut io::stdout(), capture, options.format),
+    }
 }
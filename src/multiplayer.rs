@@ -0,0 +1,532 @@
+// the guessing game for several players over tcp, everyone guesses the same secret and the server
+// announces the winner. players either take turns or race (whoever guesses first)
+//
+//   rust_book serve race --players 2              (listens on 127.0.0.1:7878)
+//   rust_book join ferris                         (in another terminal, once per player)
+//
+// the protocol is one line per message, a word and its arguments separated by spaces
+//   client -> server:  NAME <name>, GUESS <n>
+//   server -> client:  WELCOME <low> <high> <turns|race> <players>, JOINED <name>, START,
+//                      TURN <name>, RESULT <name> <guess> <too-small|too-big|correct>,
+//                      WINNER <name> <guess> <attempts>, LEFT <name>, ERROR <text>
+
+use std::{
+    fmt,
+    io::{self, BufRead, BufReader, Write},
+    net::{Shutdown, SocketAddr, TcpListener, TcpStream, ToSocketAddrs},
+    ops::RangeInclusive,
+    str::FromStr,
+    sync::mpsc,
+    thread,
+};
+
+use rand::Rng;
+
+use crate::guessing_game::{Feedback, GuessingGame, Outcome, Settings};
+
+// the port from the web server at the end of the book
+pub const DEFAULT_PORT: u16 = 7878;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Mode {
+    // one guess each, in the order the players joined
+    Turns,
+    // everyone guesses whenever they like
+    Race,
+}
+
+impl FromStr for Mode {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "turns" => Ok(Mode::Turns),
+            "race" => Ok(Mode::Race),
+            _ => Err(format!("unknown mode \"{s}\", expected turns or race")),
+        }
+    }
+}
+
+impl fmt::Display for Mode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Mode::Turns => write!(f, "turns"),
+            Mode::Race => write!(f, "race"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Verdict {
+    TooSmall,
+    TooBig,
+    Correct,
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Verdict::TooSmall => write!(f, "too-small"),
+            Verdict::TooBig => write!(f, "too-big"),
+            Verdict::Correct => write!(f, "correct"),
+        }
+    }
+}
+
+impl FromStr for Verdict {
+    type Err = ();
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "too-small" => Ok(Verdict::TooSmall),
+            "too-big" => Ok(Verdict::TooBig),
+            "correct" => Ok(Verdict::Correct),
+            _ => Err(()),
+        }
+    }
+}
+
+// what the server sends
+#[derive(Debug, Clone, PartialEq)]
+pub enum Message {
+    Welcome {
+        range: RangeInclusive<i32>,
+        mode: Mode,
+        players: usize,
+    },
+    Joined(String),
+    Start,
+    Turn(String),
+    Result {
+        player: String,
+        guess: i32,
+        verdict: Verdict,
+    },
+    Winner {
+        player: String,
+        guess: i32,
+        attempts: u32,
+    },
+    Left(String),
+    Error(String),
+}
+
+impl fmt::Display for Message {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Message::Welcome {
+                range,
+                mode,
+                players,
+            } => write!(
+                f,
+                "WELCOME {} {} {mode} {players}",
+                range.start(),
+                range.end()
+            ),
+            Message::Joined(name) => write!(f, "JOINED {name}"),
+            Message::Start => write!(f, "START"),
+            Message::Turn(name) => write!(f, "TURN {name}"),
+            Message::Result {
+                player,
+                guess,
+                verdict,
+            } => write!(f, "RESULT {player} {guess} {verdict}"),
+            Message::Winner {
+                player,
+                guess,
+                attempts,
+            } => write!(f, "WINNER {player} {guess} {attempts}"),
+            Message::Left(name) => write!(f, "LEFT {name}"),
+            Message::Error(text) => write!(f, "ERROR {text}"),
+        }
+    }
+}
+
+impl FromStr for Message {
+    type Err = String;
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
+        let bad = || format!("unexpected message \"{line}\"");
+        let words: Vec<&str> = line.split_whitespace().collect();
+        let number = |word: &str| word.parse().map_err(|_| bad());
+        let message = match words[..] {
+            ["WELCOME", low, high, mode, players] => Message::Welcome {
+                range: number(low)?..=number(high)?,
+                mode: mode.parse()?,
+                players: players.parse().map_err(|_| bad())?,
+            },
+            ["JOINED", name] => Message::Joined(name.to_string()),
+            ["START"] => Message::Start,
+            ["TURN", name] => Message::Turn(name.to_string()),
+            ["RESULT", player, guess, verdict] => Message::Result {
+                player: player.to_string(),
+                guess: number(guess)?,
+                verdict: verdict.parse().map_err(|_| bad())?,
+            },
+            ["WINNER", player, guess, attempts] => Message::Winner {
+                player: player.to_string(),
+                guess: number(guess)?,
+                attempts: attempts.parse().map_err(|_| bad())?,
+            },
+            ["LEFT", name] => Message::Left(name.to_string()),
+            ["ERROR", ..] => Message::Error(line["ERROR".len()..].trim().to_string()),
+            _ => return Err(bad()),
+        };
+        Ok(message)
+    }
+}
+
+impl Message {
+    // how `rust_book join` shows the message
+    pub fn describe(&self) -> String {
+        match self {
+            Message::Welcome {
+                range,
+                mode,
+                players,
+            } => format!(
+                "Guess a number from {} to {}, {players} players, {}.",
+                range.start(),
+                range.end(),
+                match mode {
+                    Mode::Turns => "taking turns",
+                    Mode::Race => "first to guess it wins",
+                }
+            ),
+            Message::Joined(name) => format!("{name} joined."),
+            Message::Start => String::from("Everyone's here, go!"),
+            Message::Turn(name) => format!("{name}'s turn."),
+            Message::Result {
+                player,
+                guess,
+                verdict,
+            } => match verdict {
+                Verdict::TooSmall => format!("{player} guessed {guess}: too small!"),
+                Verdict::TooBig => format!("{player} guessed {guess}: too big!"),
+                Verdict::Correct => format!("{player} guessed {guess}: correct!"),
+            },
+            Message::Winner {
+                player,
+                guess,
+                attempts,
+            } => format!(
+                "{player} wins! The number was {guess}, found in {attempts} {}.",
+                if *attempts == 1 { "guess" } else { "guesses" }
+            ),
+            Message::Left(name) => format!("{name} left the game."),
+            Message::Error(text) => format!("({text})"),
+        }
+    }
+}
+
+// what a client sends
+#[derive(Debug, Clone, PartialEq)]
+pub enum Request {
+    Name(String),
+    Guess(i32),
+}
+
+impl FromStr for Request {
+    type Err = String;
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
+        match line.split_whitespace().collect::<Vec<_>>()[..] {
+            ["NAME", name] => Ok(Request::Name(name.to_string())),
+            ["NAME", ..] => Err(String::from("a name is one word")),
+            ["GUESS", n] => n
+                .parse()
+                .map(Request::Guess)
+                .map_err(|_| format!("\"{n}\" is not a number")),
+            _ => Err(format!("expected NAME <name> or GUESS <n>, got \"{line}\"")),
+        }
+    }
+}
+
+impl fmt::Display for Request {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Request::Name(name) => write!(f, "NAME {name}"),
+            Request::Guess(n) => write!(f, "GUESS {n}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ServerSettings {
+    pub range: RangeInclusive<i32>,
+    pub mode: Mode,
+    // the game starts once this many players have joined
+    pub players: usize,
+}
+
+impl Default for ServerSettings {
+    fn default() -> Self {
+        ServerSettings {
+            range: 1..=100,
+            mode: Mode::Turns,
+            players: 2,
+        }
+    }
+}
+
+struct Player {
+    stream: TcpStream,
+    name: Option<String>,
+    connected: bool,
+    guesses: u32,
+}
+
+impl Player {
+    // a player that went away can't be written to, which is fine, they'll be told they left
+    fn send(&mut self, message: &Message) {
+        if self.connected {
+            let _ = writeln!(self.stream, "{message}");
+        }
+    }
+}
+
+pub struct Server {
+    listener: TcpListener,
+    settings: ServerSettings,
+    game: GuessingGame,
+}
+
+impl Server {
+    // port 0 picks any free port, see local_addr
+    pub fn bind(
+        addr: impl ToSocketAddrs,
+        settings: ServerSettings,
+        rng: &mut impl Rng,
+    ) -> io::Result<Self> {
+        let game_settings = Settings::new(settings.range.clone(), None)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?
+            .with_feedback(Feedback::Classic);
+        Ok(Server {
+            listener: TcpListener::bind(addr)?,
+            game: GuessingGame::new(game_settings, rng),
+            settings,
+        })
+    }
+
+    pub fn secret(&self) -> i32 {
+        self.game.secret()
+    }
+
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.listener.local_addr()
+    }
+
+    // waits for the players, plays one game and returns the winner's name
+    // (None if everyone left first)
+    pub fn run(mut self) -> io::Result<Option<String>> {
+        // every connection gets a thread that passes its lines on, None when it closes
+        let (sender, lines) = mpsc::channel::<(usize, Option<String>)>();
+        let mut players = Vec::new();
+        for id in 0..self.settings.players {
+            let (stream, _) = self.listener.accept()?;
+            let reader = BufReader::new(stream.try_clone()?);
+            let sender = sender.clone();
+            thread::spawn(move || {
+                for line in reader.lines() {
+                    let Ok(line) = line else { break };
+                    if sender.send((id, Some(line))).is_err() {
+                        return;
+                    }
+                }
+                let _ = sender.send((id, None));
+            });
+            let mut player = Player {
+                stream,
+                name: None,
+                connected: true,
+                guesses: 0,
+            };
+            player.send(&Message::Welcome {
+                range: self.settings.range.clone(),
+                mode: self.settings.mode,
+                players: self.settings.players,
+            });
+            players.push(player);
+        }
+        drop(sender);
+
+        let mut started = false;
+        let mut turn = 0;
+        while let Ok((id, line)) = lines.recv() {
+            let Some(line) = line else {
+                players[id].connected = false;
+                if let Some(name) = players[id].name.clone() {
+                    broadcast(&mut players, &Message::Left(name));
+                }
+                if players.iter().all(|player| !player.connected) {
+                    return Ok(None);
+                }
+                // leaving before sending a name might have been all the others were waiting for
+                if !started && everyone_named(&players) {
+                    started = true;
+                    turn = begin(&mut players, self.settings.mode);
+                } else if started && self.settings.mode == Mode::Turns && turn == id {
+                    turn = next_turn(&players, turn);
+                    announce_turn(&mut players, turn);
+                }
+                continue;
+            };
+            let request = match line.parse::<Request>() {
+                Ok(request) => request,
+                Err(e) => {
+                    players[id].send(&Message::Error(e));
+                    continue;
+                }
+            };
+            match request {
+                Request::Name(_) if players[id].name.is_some() => {
+                    players[id].send(&Message::Error(String::from("you already have a name")));
+                }
+                Request::Name(name) => {
+                    if players
+                        .iter()
+                        .any(|player| player.name.as_ref() == Some(&name))
+                    {
+                        players[id].send(&Message::Error(format!("{name} is taken")));
+                        continue;
+                    }
+                    players[id].name = Some(name.clone());
+                    broadcast(&mut players, &Message::Joined(name));
+                    if everyone_named(&players) {
+                        started = true;
+                        turn = begin(&mut players, self.settings.mode);
+                    }
+                }
+                Request::Guess(_) if !started => {
+                    players[id].send(&Message::Error(String::from(
+                        "the game starts when everyone has joined",
+                    )));
+                }
+                Request::Guess(_) if self.settings.mode == Mode::Turns && turn != id => {
+                    let whose = players[turn].name.clone().unwrap_or_default();
+                    players[id].send(&Message::Error(format!("it's {whose}'s turn")));
+                }
+                Request::Guess(guess) => {
+                    let verdict = match self.game.guess(guess) {
+                        Outcome::TooSmall => Verdict::TooSmall,
+                        Outcome::TooBig => Verdict::TooBig,
+                        Outcome::Correct => Verdict::Correct,
+                        Outcome::OutOfRange(e) => {
+                            players[id].send(&Message::Error(e.to_string()));
+                            continue;
+                        }
+                        // classic feedback and no attempt limit, so nothing else can happen
+                        _ => continue,
+                    };
+                    players[id].guesses += 1;
+                    let player = players[id].name.clone().unwrap_or_default();
+                    broadcast(
+                        &mut players,
+                        &Message::Result {
+                            player: player.clone(),
+                            guess,
+                            verdict,
+                        },
+                    );
+                    if verdict == Verdict::Correct {
+                        let attempts = players[id].guesses;
+                        broadcast(
+                            &mut players,
+                            &Message::Winner {
+                                player: player.clone(),
+                                guess,
+                                attempts,
+                            },
+                        );
+                        // the reader threads hold clones of the streams, shutting them down is
+                        // what actually closes the connections
+                        for player in &players {
+                            let _ = player.stream.shutdown(Shutdown::Both);
+                        }
+                        return Ok(Some(player));
+                    }
+                    if self.settings.mode == Mode::Turns {
+                        turn = next_turn(&players, turn);
+                        announce_turn(&mut players, turn);
+                    }
+                }
+            }
+        }
+        Ok(None)
+    }
+}
+
+fn broadcast(players: &mut [Player], message: &Message) {
+    for player in players {
+        player.send(message);
+    }
+}
+
+// the game can start, the players who left before giving a name aren't waited for
+fn everyone_named(players: &[Player]) -> bool {
+    players
+        .iter()
+        .all(|player| player.name.is_some() || !player.connected)
+}
+
+// starts the game and returns whose turn is first, the first player still connected
+fn begin(players: &mut [Player], mode: Mode) -> usize {
+    broadcast(players, &Message::Start);
+    let turn = next_turn(players, players.len() - 1);
+    if mode == Mode::Turns {
+        announce_turn(players, turn);
+    }
+    turn
+}
+
+// the next player still connected after `turn`, going round
+fn next_turn(players: &[Player], turn: usize) -> usize {
+    (1..=players.len())
+        .map(|step| (turn + step) % players.len())
+        .find(|i| players[*i].connected)
+        .unwrap_or(turn)
+}
+
+fn announce_turn(players: &mut [Player], turn: usize) {
+    if let Some(name) = players[turn].name.clone() {
+        broadcast(players, &Message::Turn(name));
+    }
+}
+
+// one player's connection
+pub struct Client {
+    reader: BufReader<TcpStream>,
+    writer: TcpStream,
+}
+
+impl Client {
+    // connects and sends the name, the server answers with WELCOME then JOINED
+    pub fn connect(addr: impl ToSocketAddrs, name: &str) -> io::Result<Self> {
+        let writer = TcpStream::connect(addr)?;
+        let reader = BufReader::new(writer.try_clone()?);
+        let mut client = Client { reader, writer };
+        client.send(&Request::Name(name.to_string()))?;
+        Ok(client)
+    }
+
+    pub fn stream(&self) -> &TcpStream {
+        &self.writer
+    }
+
+    pub fn send(&mut self, request: &Request) -> io::Result<()> {
+        writeln!(self.writer, "{request}")
+    }
+
+    pub fn guess(&mut self, n: i32) -> io::Result<()> {
+        self.send(&Request::Guess(n))
+    }
+
+    // the next message from the server, None once it has closed the connection
+    pub fn receive(&mut self) -> io::Result<Option<Message>> {
+        let mut line = String::new();
+        if self.reader.read_line(&mut line)? == 0 {
+            return Ok(None);
+        }
+        line.trim()
+            .parse()
+            .map(Some)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+}
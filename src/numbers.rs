@@ -0,0 +1,176 @@
+// numbers written out for people rather than for rust: 21st, twenty-one and twenty-first, for
+// every integer type from i8 to u128
+//
+//   numbers::ordinal(112u8)                    "112th"
+//   numbers::cardinal_words(-40i32)            "minus forty"
+//   numbers::ordinal_words(1_000_001u64)       "one million and first"
+//   numbers::cardinal_words(u128::MAX)         "three hundred and forty undecillion two hundred and ..."
+//
+// the words are british english, so there's an "and" before the tens: "one hundred and one"
+
+// the integer types, all of them fit in a u128 once the sign is taken off (even i128::MIN)
+pub trait Integer: Copy {
+    fn is_negative(self) -> bool;
+    // the size without the sign
+    fn magnitude(self) -> u128;
+}
+
+macro_rules! unsigned {
+    ($($t:ty),+) => {
+        $(
+            impl Integer for $t {
+                fn is_negative(self) -> bool {
+                    false
+                }
+                fn magnitude(self) -> u128 {
+                    self as u128
+                }
+            }
+        )+
+    };
+}
+
+macro_rules! signed {
+    ($($t:ty),+) => {
+        $(
+            impl Integer for $t {
+                fn is_negative(self) -> bool {
+                    self < 0
+                }
+                fn magnitude(self) -> u128 {
+                    self.unsigned_abs() as u128
+                }
+            }
+        )+
+    };
+}
+
+unsigned!(u8, u16, u32, u64, u128, usize);
+signed!(i8, i16, i32, i64, i128, isize);
+
+const ONES: [&str; 20] = [
+    "zero",
+    "one",
+    "two",
+    "three",
+    "four",
+    "five",
+    "six",
+    "seven",
+    "eight",
+    "nine",
+    "ten",
+    "eleven",
+    "twelve",
+    "thirteen",
+    "fourteen",
+    "fifteen",
+    "sixteen",
+    "seventeen",
+    "eighteen",
+    "nineteen",
+];
+
+const TENS: [&str; 10] = [
+    "", "", "twenty", "thirty", "forty", "fifty", "sixty", "seventy", "eighty", "ninety",
+];
+
+// the short scale, a thousand times bigger each time. u128::MAX is 340 undecillion and a bit
+const SCALES: [&str; 13] = [
+    "",
+    "thousand",
+    "million",
+    "billion",
+    "trillion",
+    "quadrillion",
+    "quintillion",
+    "sextillion",
+    "septillion",
+    "octillion",
+    "nonillion",
+    "decillion",
+    "undecillion",
+];
+
+// 1st, 2nd, 3rd, 4th, ... 11th, 12th, 13th, ... 21st, 22nd, ... 111th, 112th
+pub fn ordinal<T: Integer>(n: T) -> String {
+    let magnitude = n.magnitude();
+    let suffix = match (magnitude % 10, magnitude % 100) {
+        (_, 11..=13) => "th",
+        (1, _) => "st",
+        (2, _) => "nd",
+        (3, _) => "rd",
+        _ => "th",
+    };
+    let sign = if n.is_negative() { "-" } else { "" };
+    format!("{sign}{magnitude}{suffix}")
+}
+
+// zero, forty-two, one hundred and one, minus one thousand two hundred
+pub fn cardinal_words<T: Integer>(n: T) -> String {
+    let mut magnitude = n.magnitude();
+    if magnitude == 0 {
+        return String::from(ONES[0]);
+    }
+    // the groups of three digits, lowest first
+    let mut groups = Vec::new();
+    while magnitude > 0 {
+        groups.push((magnitude % 1000) as usize);
+        magnitude /= 1000;
+    }
+    let mut words = Vec::new();
+    if n.is_negative() {
+        words.push(String::from("minus"));
+    }
+    for (scale, group) in groups.iter().enumerate().rev() {
+        if *group == 0 {
+            continue;
+        }
+        // "one thousand and five", the and goes in when the last group has no hundreds
+        if scale == 0 && *group < 100 && groups.len() > 1 {
+            words.push(String::from("and"));
+        }
+        words.push(hundreds(*group));
+        if scale > 0 {
+            words.push(SCALES[scale].to_string());
+        }
+    }
+    words.join(" ")
+}
+
+// zeroth, first, twenty-first, one hundredth, one million and second
+pub fn ordinal_words<T: Integer>(n: T) -> String {
+    let words = cardinal_words(n);
+    // only the last word changes, and only the part after the hyphen in twenty-one
+    let split = words.rfind([' ', '-']).map_or(0, |i| i + 1);
+    let (start, last) = words.split_at(split);
+    let last = match last {
+        "one" => String::from("first"),
+        "two" => String::from("second"),
+        "three" => String::from("third"),
+        "five" => String::from("fifth"),
+        "eight" => String::from("eighth"),
+        "nine" => String::from("ninth"),
+        "twelve" => String::from("twelfth"),
+        // twenty to twentieth
+        tens if tens.ends_with('y') => format!("{}ieth", &tens[..tens.len() - 1]),
+        other => format!("{other}th"),
+    };
+    format!("{start}{last}")
+}
+
+// 1 to 999
+fn hundreds(n: usize) -> String {
+    let rest = n % 100;
+    let tens = match (rest / 10, rest % 10) {
+        (0, 0) => String::new(),
+        (0 | 1, _) => ONES[rest].to_string(),
+        (tens, 0) => TENS[tens].to_string(),
+        (tens, ones) => format!("{}-{}", TENS[tens], ONES[ones]),
+    };
+    match (n / 100, rest) {
+        (0, _) => tens,
+        (h, 0) => format!("{} hundred", ONES[h]),
+        (h, _) => format!("{} hundred and {tens}", ONES[h]),
+    }
+}
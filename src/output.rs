@@ -0,0 +1,208 @@
+// chapters write through a Report instead of println! so their output can be printed straight away,
+// captured in memory (for tests) or rendered afterwards as plain text, markdown or json
+
+use std::{
+    fmt,
+    fs::File,
+    io::{self, Write},
+    path::Path,
+    str::FromStr,
+};
+
+pub trait Report {
+    // a chapter or section title
+    fn heading(&mut self, text: &str);
+    // one line of output, like println!
+    fn line(&mut self, text: &str);
+    // asks for input, the terminal keeps the cursor on the same line
+    fn prompt(&mut self, text: &str) {
+        self.line(text);
+    }
+}
+
+// println! for a Report: say!(out, "x is {}", x)
+macro_rules! say {
+    ($out:expr) => {
+        $out.line("")
+    };
+    ($out:expr, $($arg:tt)*) => {
+        $out.line(&format!($($arg)*))
+    };
+}
+pub(crate) use say;
+
+// prints as it goes, what the chapters used to do with println!
+pub struct Stdout;
+impl Report for Stdout {
+    fn heading(&mut self, text: &str) {
+        println!("{text}");
+    }
+    fn line(&mut self, text: &str) {
+        println!("{text}");
+    }
+    fn prompt(&mut self, text: &str) {
+        print!("{text}");
+        // print! doesn't flush on its own, without this the prompt shows up after the input
+        let _ = io::stdout().flush();
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Event {
+    Heading(String),
+    Line(String),
+}
+
+// keeps everything in memory so it can be checked or rendered later
+#[derive(Debug, Default)]
+pub struct Capture {
+    pub events: Vec<Event>,
+}
+impl Capture {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    pub fn render(&self, format: Format) -> String {
+        format.render(&self.events)
+    }
+}
+impl Report for Capture {
+    fn heading(&mut self, text: &str) {
+        self.events.push(Event::Heading(text.to_string()));
+    }
+    fn line(&mut self, text: &str) {
+        self.events.push(Event::Line(text.to_string()));
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Format {
+    Plain,
+    Markdown,
+    Json,
+}
+
+impl Format {
+    pub fn render(&self, events: &[Event]) -> String {
+        match self {
+            Format::Plain => render_plain(events),
+            Format::Markdown => render_markdown(events),
+            Format::Json => render_json(events),
+        }
+    }
+}
+
+impl FromStr for Format {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "plain" | "text" | "txt" => Ok(Format::Plain),
+            "markdown" | "md" => Ok(Format::Markdown),
+            "json" => Ok(Format::Json),
+            _ => Err(format!(
+                "unknown format \"{s}\", expected plain, markdown or json"
+            )),
+        }
+    }
+}
+
+impl fmt::Display for Format {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Format::Plain => "plain",
+            Format::Markdown => "markdown",
+            Format::Json => "json",
+        };
+        write!(f, "{name}")
+    }
+}
+
+fn render_plain(events: &[Event]) -> String {
+    let mut text = String::new();
+    for event in events {
+        match event {
+            Event::Heading(line) | Event::Line(line) => {
+                text.push_str(line);
+                text.push('\n');
+            }
+        }
+    }
+    text
+}
+
+// headings become markdown headings and runs of lines go into a fenced block
+fn render_markdown(events: &[Event]) -> String {
+    let mut text = String::new();
+    let mut in_block = false;
+    for event in events {
+        match event {
+            Event::Heading(heading) => {
+                if in_block {
+                    text.push_str("```\n\n");
+                    in_block = false;
+                }
+                text.push_str(&format!("## {heading}\n\n"));
+            }
+            Event::Line(line) => {
+                if !in_block {
+                    text.push_str("```text\n");
+                    in_block = true;
+                }
+                text.push_str(line);
+                text.push('\n');
+            }
+        }
+    }
+    if in_block {
+        text.push_str("```\n");
+    }
+    text
+}
+
+fn render_json(events: &[Event]) -> String {
+    let items: Vec<String> = events
+        .iter()
+        .map(|event| match event {
+            Event::Heading(text) => format!(
+                "  {{\"kind\": \"heading\", \"text\": {}}}",
+                json_string(text)
+            ),
+            Event::Line(text) => {
+                format!("  {{\"kind\": \"line\", \"text\": {}}}", json_string(text))
+            }
+        })
+        .collect();
+    if items.is_empty() {
+        String::from("[]\n")
+    } else {
+        format!("[\n{}\n]\n", items.join(",\n"))
+    }
+}
+
+// quotes and escapes a string for json
+pub fn json_string(text: &str) -> String {
+    let mut quoted = String::from("\"");
+    for c in text.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            c if (c as u32) < 0x20 => quoted.push_str(&format!("\\u{:04x}", c as u32)),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+// renders captured output into a file (or anything else that implements Write)
+pub fn write_to(writer: &mut impl Write, capture: &Capture, format: Format) -> io::Result<()> {
+    writer.write_all(capture.render(format).as_bytes())
+}
+
+pub fn write_file(path: &Path, capture: &Capture, format: Format) -> io::Result<()> {
+    let mut file = File::create(path)?;
+    write_to(&mut file, capture, format)
+}
@@ -0,0 +1,505 @@
+// what happens when integer arithmetic overflows, for every width from i8 to u128: the
+// wrapping_*, checked_*, overflowing_* and saturating_* answers, and whether the plain operator
+// would panic in a debug build (release builds wrap instead, except for dividing)
+//
+//   rust_book overflow 250 + 10
+//   rust_book overflow MAX + 1           MAX and MIN are each type's own
+//   rust_book overflow MIN / -1          even release builds panic here
+//   rust_book overflow 1 << 9
+//   rust_book overflow neg MIN
+//
+//   type  wrapping  checked   overflowing  saturating  debug  release
+//   u8    4         None      (4, true)    255         panic  4
+
+use std::{fmt, io::BufRead, str::FromStr};
+
+use crate::output::{say, Report};
+use crate::repl;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Op {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Rem,
+    Pow,
+    Shl,
+    Shr,
+    Neg,
+}
+
+impl Op {
+    pub const ALL: [Op; 9] = [
+        Op::Add,
+        Op::Sub,
+        Op::Mul,
+        Op::Div,
+        Op::Rem,
+        Op::Pow,
+        Op::Shl,
+        Op::Shr,
+        Op::Neg,
+    ];
+
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            Op::Add => "+",
+            Op::Sub => "-",
+            Op::Mul => "*",
+            Op::Div => "/",
+            Op::Rem => "%",
+            Op::Pow => "pow",
+            Op::Shl => "<<",
+            Op::Shr => ">>",
+            Op::Neg => "neg",
+        }
+    }
+
+    // what a debug build panics with when the plain operator overflows
+    fn overflow_message(&self) -> &'static str {
+        match self {
+            Op::Add => "attempt to add with overflow",
+            Op::Sub => "attempt to subtract with overflow",
+            // pow is repeated multiplying
+            Op::Mul | Op::Pow => "attempt to multiply with overflow",
+            Op::Div => "attempt to divide with overflow",
+            Op::Rem => "attempt to calculate the remainder with overflow",
+            Op::Shl => "attempt to shift left with overflow",
+            Op::Shr => "attempt to shift right with overflow",
+            Op::Neg => "attempt to negate with overflow",
+        }
+    }
+}
+
+impl FromStr for Op {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Op::ALL
+            .into_iter()
+            .find(|op| op.symbol() == s.to_lowercase())
+            .ok_or_else(|| {
+                let symbols: Vec<&str> = Op::ALL.iter().map(Op::symbol).collect();
+                format!(
+                    "unknown operation \"{s}\", expected one of {}",
+                    symbols.join(" ")
+                )
+            })
+    }
+}
+
+impl fmt::Display for Op {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.symbol())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Width {
+    I8,
+    I16,
+    I32,
+    I64,
+    I128,
+    U8,
+    U16,
+    U32,
+    U64,
+    U128,
+}
+
+impl Width {
+    pub const ALL: [Width; 10] = [
+        Width::I8,
+        Width::I16,
+        Width::I32,
+        Width::I64,
+        Width::I128,
+        Width::U8,
+        Width::U16,
+        Width::U32,
+        Width::U64,
+        Width::U128,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Width::I8 => "i8",
+            Width::I16 => "i16",
+            Width::I32 => "i32",
+            Width::I64 => "i64",
+            Width::I128 => "i128",
+            Width::U8 => "u8",
+            Width::U16 => "u16",
+            Width::U32 => "u32",
+            Width::U64 => "u64",
+            Width::U128 => "u128",
+        }
+    }
+}
+
+impl fmt::Display for Width {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+// `a op b`, or `neg a`. the operands stay text until a width is picked, since 300 is fine as an
+// i16 but not as an i8 and MAX is different for every type
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Expression {
+    pub op: Op,
+    pub a: String,
+    pub b: Option<String>,
+}
+
+impl FromStr for Expression {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let words: Vec<&str> = s.split_whitespace().collect();
+        // the numbers are checked against each width later, anything else can go now
+        for word in &words {
+            let is_op = word.parse::<Op>().is_ok();
+            if !is_op && !is_number(word) {
+                return Err(format!("\"{word}\" isn't a whole number, MAX or MIN"));
+            }
+        }
+        match words[..] {
+            [op, a] if op.eq_ignore_ascii_case("neg") => Ok(Expression {
+                op: Op::Neg,
+                a: a.to_string(),
+                b: None,
+            }),
+            [a, op, b] => match op.parse()? {
+                Op::Neg => Err(String::from("neg takes one number, e.g. `neg MIN`")),
+                op => Ok(Expression {
+                    op,
+                    a: a.to_string(),
+                    b: Some(b.to_string()),
+                }),
+            },
+            _ => Err(format!(
+                "expected something like `250 + 10`, `MAX * 2` or `neg MIN`, got \"{s}\""
+            )),
+        }
+    }
+}
+
+impl fmt::Display for Expression {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.b {
+            Some(b) => write!(f, "{} {} {b}", self.a, self.op),
+            None => write!(f, "{} {}", self.op, self.a),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    // the answer, as text since every width has its own type
+    Value(String),
+    // checked_* gave None
+    Nothing,
+    // overflowing_*: the wrapped answer and whether it overflowed
+    Flagged(String, bool),
+    Panic(&'static str),
+    // there's no method (saturating_rem) or the operator doesn't compile (-x for a u8)
+    Missing,
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Outcome::Value(value) => write!(f, "{value}"),
+            Outcome::Nothing => write!(f, "None"),
+            Outcome::Flagged(value, overflowed) => write!(f, "({value}, {overflowed})"),
+            Outcome::Panic(_) => write!(f, "panic"),
+            Outcome::Missing => write!(f, "-"),
+        }
+    }
+}
+
+// one width's answers
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Results {
+    pub wrapping: Outcome,
+    pub checked: Outcome,
+    pub overflowing: Outcome,
+    pub saturating: Outcome,
+    // the plain operator, `a + b`
+    pub debug: Outcome,
+    pub release: Outcome,
+}
+
+// the same for every op that wraps in release builds and only panics in debug ones
+fn wrapped<T: ToString>(
+    op: Op,
+    wrapping: T,
+    checked: Option<T>,
+    (overflowed_value, overflowed): (T, bool),
+    saturating: Option<T>,
+) -> Results {
+    let debug = match overflowed {
+        true => Outcome::Panic(op.overflow_message()),
+        false => Outcome::Value(wrapping.to_string()),
+    };
+    Results {
+        release: Outcome::Value(wrapping.to_string()),
+        wrapping: Outcome::Value(wrapping.to_string()),
+        checked: checked.map_or(Outcome::Nothing, |value| {
+            Outcome::Value(format!("Some({})", value.to_string()))
+        }),
+        overflowing: Outcome::Flagged(overflowed_value.to_string(), overflowed),
+        saturating: saturating.map_or(Outcome::Missing, |value| Outcome::Value(value.to_string())),
+        debug,
+    }
+}
+
+// dividing by zero panics whatever the build and whichever method, apart from checked_*. MIN / -1,
+// the one division that overflows, panics in release builds too but the methods cope with it
+fn divided<T: ToString>(
+    op: Op,
+    by_zero: bool,
+    checked: Option<T>,
+    overflowing: (T, bool),
+    saturating: Option<T>,
+) -> Results {
+    if by_zero {
+        let message = match op {
+            Op::Div => "attempt to divide by zero",
+            _ => "attempt to calculate the remainder with a divisor of zero",
+        };
+        return Results {
+            wrapping: Outcome::Panic(message),
+            checked: Outcome::Nothing,
+            overflowing: Outcome::Panic(message),
+            saturating: saturating.map_or(Outcome::Missing, |_| Outcome::Panic(message)),
+            debug: Outcome::Panic(message),
+            release: Outcome::Panic(message),
+        };
+    }
+    let (value, overflowed) = overflowing;
+    let plain = match overflowed {
+        true => Outcome::Panic(op.overflow_message()),
+        false => Outcome::Value(value.to_string()),
+    };
+    Results {
+        wrapping: Outcome::Value(value.to_string()),
+        checked: checked.map_or(Outcome::Nothing, |value| {
+            Outcome::Value(format!("Some({})", value.to_string()))
+        }),
+        overflowing: Outcome::Flagged(value.to_string(), overflowed),
+        saturating: saturating.map_or(Outcome::Missing, |value| Outcome::Value(value.to_string())),
+        debug: plain.clone(),
+        release: plain,
+    }
+}
+
+// fits in some integer type, or is MAX or MIN
+fn is_number(text: &str) -> bool {
+    let digits = text.replace('_', "");
+    ["MAX", "MIN"].contains(&text.to_uppercase().as_str())
+        || digits.parse::<i128>().is_ok()
+        || digits.parse::<u128>().is_ok()
+}
+
+// a number, or MAX or MIN of the type, underscores allowed
+fn operand<T: FromStr>(text: &str, max: T, min: T, width: Width) -> Result<T, String> {
+    match text.to_uppercase().as_str() {
+        "MAX" => Ok(max),
+        "MIN" => Ok(min),
+        _ => text
+            .replace('_', "")
+            .parse()
+            .map_err(|_| format!("{text} is out of range for {width}")),
+    }
+}
+
+// shifts and powers take a u32 on the right whatever the type on the left
+fn exponent(text: Option<&str>) -> Result<u32, String> {
+    let text = text.unwrap_or_default();
+    text.replace('_', "")
+        .parse()
+        .map_err(|_| format!("{text} isn't a u32, shifts and powers take a u32 on the right"))
+}
+
+macro_rules! evaluate {
+    ($width:expr, $t:ty, $expression:expr, $neg:ident) => {{
+        let Expression { op, a, b } = $expression;
+        let a: $t = operand(a, <$t>::MAX, <$t>::MIN, $width)?;
+        let b = b.as_deref();
+        let rhs = || operand::<$t>(b.unwrap_or_default(), <$t>::MAX, <$t>::MIN, $width);
+        Ok(match op {
+            Op::Add => {
+                let b = rhs()?;
+                wrapped(*op, a.wrapping_add(b), a.checked_add(b), a.overflowing_add(b), Some(a.saturating_add(b)))
+            }
+            Op::Sub => {
+                let b = rhs()?;
+                wrapped(*op, a.wrapping_sub(b), a.checked_sub(b), a.overflowing_sub(b), Some(a.saturating_sub(b)))
+            }
+            Op::Mul => {
+                let b = rhs()?;
+                wrapped(*op, a.wrapping_mul(b), a.checked_mul(b), a.overflowing_mul(b), Some(a.saturating_mul(b)))
+            }
+            Op::Div => {
+                let b = rhs()?;
+                match b {
+                    0 => divided::<$t>(*op, true, None, (0, false), Some(0)),
+                    b => divided(*op, false, a.checked_div(b), a.overflowing_div(b), Some(a.saturating_div(b))),
+                }
+            }
+            Op::Rem => {
+                let b = rhs()?;
+                match b {
+                    0 => divided::<$t>(*op, true, None, (0, false), None),
+                    b => divided(*op, false, a.checked_rem(b), a.overflowing_rem(b), None),
+                }
+            }
+            Op::Pow => {
+                let b = exponent(b)?;
+                wrapped(*op, a.wrapping_pow(b), a.checked_pow(b), a.overflowing_pow(b), Some(a.saturating_pow(b)))
+            }
+            Op::Shl => {
+                let b = exponent(b)?;
+                wrapped(*op, a.wrapping_shl(b), a.checked_shl(b), a.overflowing_shl(b), None)
+            }
+            Op::Shr => {
+                let b = exponent(b)?;
+                wrapped(*op, a.wrapping_shr(b), a.checked_shr(b), a.overflowing_shr(b), None)
+            }
+            Op::Neg => evaluate!(@neg $neg, op, a),
+        })
+    }};
+    (@neg signed, $op:expr, $a:expr) => {
+        wrapped(*$op, $a.wrapping_neg(), $a.checked_neg(), $a.overflowing_neg(), Some($a.saturating_neg()))
+    };
+    // the methods are there (everything but 0 overflows) but -x doesn't compile
+    (@neg unsigned, $op:expr, $a:expr) => {{
+        let results = wrapped(*$op, $a.wrapping_neg(), $a.checked_neg(), $a.overflowing_neg(), None);
+        Results {
+            debug: Outcome::Missing,
+            release: Outcome::Missing,
+            ..results
+        }
+    }};
+}
+
+// the answers for one width, or why the operands don't fit it
+pub fn evaluate(width: Width, expression: &Expression) -> Result<Results, String> {
+    match width {
+        Width::I8 => evaluate!(width, i8, expression, signed),
+        Width::I16 => evaluate!(width, i16, expression, signed),
+        Width::I32 => evaluate!(width, i32, expression, signed),
+        Width::I64 => evaluate!(width, i64, expression, signed),
+        Width::I128 => evaluate!(width, i128, expression, signed),
+        Width::U8 => evaluate!(width, u8, expression, unsigned),
+        Width::U16 => evaluate!(width, u16, expression, unsigned),
+        Width::U32 => evaluate!(width, u32, expression, unsigned),
+        Width::U64 => evaluate!(width, u64, expression, unsigned),
+        Width::U128 => evaluate!(width, u128, expression, unsigned),
+    }
+}
+
+pub fn explore(expression: &Expression) -> Vec<(Width, Result<Results, String>)> {
+    Width::ALL
+        .into_iter()
+        .map(|width| (width, evaluate(width, expression)))
+        .collect()
+}
+
+// a table with a row for every width, then what the panics say
+pub fn report(expression: &Expression, out: &mut dyn Report) {
+    let headers = [
+        "type",
+        "wrapping",
+        "checked",
+        "overflowing",
+        "saturating",
+        "debug",
+        "release",
+    ];
+    let explored = explore(expression);
+    let rows: Vec<Vec<String>> = explored
+        .iter()
+        .map(|(width, results)| {
+            let mut row = vec![width.to_string()];
+            match results {
+                Ok(results) => row.extend(
+                    [
+                        &results.wrapping,
+                        &results.checked,
+                        &results.overflowing,
+                        &results.saturating,
+                        &results.debug,
+                        &results.release,
+                    ]
+                    .map(Outcome::to_string),
+                ),
+                Err(e) => row.push(format!("({e})")),
+            }
+            row
+        })
+        .collect();
+    // the widths leave out the rows that are just a note, they'd push everything over
+    let widths: Vec<usize> = (0..headers.len())
+        .map(|column| {
+            rows.iter()
+                .filter(|row| row.len() == headers.len())
+                .map(|row| row[column].chars().count())
+                .chain([headers[column].len()])
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+    let line = |cells: &[String]| {
+        let cells: Vec<String> = cells
+            .iter()
+            .zip(&widths)
+            .map(|(cell, width)| format!("{cell:<width$}"))
+            .collect();
+        cells.join("  ").trim_end().to_string()
+    };
+
+    out.heading(&expression.to_string());
+    let headers: Vec<String> = headers.iter().map(|header| header.to_string()).collect();
+    say!(out, "{}", line(&headers));
+    for row in &rows {
+        say!(out, "{}", line(row));
+    }
+
+    let mut panics: Vec<&'static str> = Vec::new();
+    for (_, results) in &explored {
+        let Ok(results) = results else { continue };
+        for outcome in [&results.wrapping, &results.debug, &results.release] {
+            if let Outcome::Panic(message) = outcome {
+                if !panics.contains(message) {
+                    panics.push(message);
+                }
+            }
+        }
+    }
+    for message in panics {
+        say!(out, "panic: \"{message}\"");
+    }
+    if expression.op == Op::Neg {
+        say!(
+            out,
+            "-x doesn't compile for unsigned types, the methods are still there"
+        );
+    }
+}
+
+// overflow explorer in the repl, one expression per line
+pub fn explorer(input: &mut dyn BufRead, out: &mut dyn Report) {
+    say!(
+        out,
+        "Type a sum like 250 + 10, MAX * 2, MIN / -1, 1 << 9 or neg MIN to see it in every integer type, \"exit\" to stop."
+    );
+    while let Some(line) = repl::prompt_line(input, out, "overflow> ") {
+        match line.parse::<Expression>() {
+            Ok(expression) => report(&expression, out),
+            Err(e) => say!(out, "{e}"),
+        }
+    }
+}
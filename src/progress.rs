@@ -0,0 +1,165 @@
+// remembers which chapters and sections have been run, by whom, when and whether they passed
+// (a chapter passes when it runs without panicking)
+// the runs are appended to progress.tsv in the data directory, one tab separated line each:
+//   user  chapter  section (- for the whole chapter)  unix time  passed|failed
+
+use std::{
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::{Path, PathBuf},
+};
+
+use crate::chapter::{Chapter, CHAPTERS};
+use crate::output::{say, Report};
+use crate::storage;
+
+pub const FILE_NAME: &str = "progress.tsv";
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Entry {
+    pub user: String,
+    pub chapter: String,
+    // None when the whole chapter was run
+    pub section: Option<String>,
+    pub timestamp: u64,
+    pub passed: bool,
+}
+
+impl Entry {
+    // a run that just finished, for the current user
+    pub fn now(chapter: &str, section: Option<&str>, passed: bool) -> Self {
+        Entry {
+            user: storage::current_user(),
+            chapter: chapter.to_string(),
+            section: section.map(str::to_string),
+            timestamp: storage::now(),
+            passed,
+        }
+    }
+
+    fn to_line(&self) -> String {
+        // like the scores file, a tab or newline in the name would split the line
+        format!(
+            "{}\t{}\t{}\t{}\t{}",
+            self.user.replace(['\t', '\n', '\r'], " "),
+            self.chapter,
+            self.section.as_deref().unwrap_or("-"),
+            self.timestamp,
+            if self.passed { "passed" } else { "failed" }
+        )
+    }
+
+    fn from_line(line: &str) -> Option<Self> {
+        let fields: Vec<&str> = line.split('\t').collect();
+        match fields[..] {
+            [user, chapter, section, timestamp, passed] => Some(Entry {
+                user: user.to_string(),
+                chapter: chapter.to_string(),
+                section: (section != "-").then(|| section.to_string()),
+                timestamp: timestamp.parse().ok()?,
+                passed: match passed {
+                    "passed" => true,
+                    "failed" => false,
+                    _ => return None,
+                },
+            }),
+            _ => None,
+        }
+    }
+}
+
+pub fn default_path() -> io::Result<PathBuf> {
+    storage::data_file(FILE_NAME)
+}
+
+// every entry in the file, lines that can't be read are skipped and a missing file is no progress yet
+pub fn load(path: &Path) -> io::Result<Vec<Entry>> {
+    match fs::read_to_string(path) {
+        Ok(text) => Ok(text.lines().filter_map(Entry::from_line).collect()),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Vec::new()),
+        Err(e) => Err(e),
+    }
+}
+
+pub fn record(path: &Path, entries: &[Entry]) -> io::Result<()> {
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    for entry in entries {
+        writeln!(file, "{}", entry.to_line())?;
+    }
+    Ok(())
+}
+
+// has this section (or the chapter it belongs to, run as a whole) passed for the user?
+fn section_passed(entries: &[&Entry], chapter: &str, section: &str) -> bool {
+    entries.iter().any(|entry| {
+        entry.passed
+            && entry.chapter == chapter
+            && entry.section.as_deref().is_none_or(|name| name == section)
+    })
+}
+
+fn chapter_passed(entries: &[&Entry], chapter: &dyn Chapter) -> bool {
+    let id = chapter.id();
+    if chapter.sections().is_empty() {
+        entries
+            .iter()
+            .any(|entry| entry.passed && entry.chapter == id)
+    } else {
+        chapter
+            .sections()
+            .iter()
+            .all(|section| section_passed(entries, &id, section.name))
+    }
+}
+
+// the first chapter (and section) in book order the user hasn't passed yet
+pub fn next_up(entries: &[Entry], user: &str) -> Option<(String, Option<&'static str>)> {
+    let entries: Vec<&Entry> = entries.iter().filter(|entry| entry.user == user).collect();
+    CHAPTERS
+        .iter()
+        .find(|chapter| !chapter_passed(&entries, **chapter))
+        .map(|chapter| {
+            let id = chapter.id();
+            let section = chapter
+                .sections()
+                .iter()
+                .find(|section| !section_passed(&entries, &id, section.name))
+                .map(|section| section.name);
+            (id, section)
+        })
+}
+
+pub fn summary(entries: &[Entry], user: &str, out: &mut dyn Report) {
+    out.heading(&format!("Progress for {user}"));
+    let mine: Vec<&Entry> = entries.iter().filter(|entry| entry.user == user).collect();
+
+    for chapter in CHAPTERS {
+        let id = chapter.id();
+        let runs: Vec<&&Entry> = mine.iter().filter(|entry| entry.chapter == id).collect();
+        let done = if chapter.sections().is_empty() {
+            format!("{}/1", u8::from(chapter_passed(&mine, *chapter)))
+        } else {
+            let passed = chapter
+                .sections()
+                .iter()
+                .filter(|section| section_passed(&mine, &id, section.name))
+                .count();
+            format!("{passed}/{}", chapter.sections().len())
+        };
+        let last = match runs.iter().max_by_key(|entry| entry.timestamp) {
+            Some(entry) => format!(
+                "last run {}{}",
+                storage::format_timestamp(entry.timestamp),
+                if entry.passed { "" } else { " (failed)" }
+            ),
+            None => String::from("not started"),
+        };
+        say!(out, "{id:>5}  {:<30} {done:>5}  {last}", chapter.title());
+    }
+
+    match next_up(entries, user) {
+        Some((id, Some(section))) => say!(out, "next up: `rust_book run {id} {section}`"),
+        Some((id, None)) => say!(out, "next up: `rust_book run {id}`"),
+        None => say!(out, "every chapter done, nice one!"),
+    }
+}
@@ -0,0 +1,174 @@
+// quiz on the snippets in snippets.rs: for each one guess whether it fails to compile, panics or runs
+// fine, then pick the reason why
+//
+//   What happens when this runs?  [c]ompile error, [p]anic or [s]uccess
+//   quiz> c
+//   Right, it does not compile (error[E0499]).
+//   Why?
+//     1) ...
+
+use std::io::BufRead;
+
+use rand::{seq::SliceRandom, Rng};
+
+use crate::output::{say, Report};
+use crate::repl::prompt_line;
+use crate::snippets::{self, Outcome, Reason, Snippet};
+
+pub const PROMPT: &str = "quiz> ";
+// how many reasons to choose from, including the right one
+const CHOICES: usize = 4;
+
+// a point for each outcome and each reason guessed right
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Score {
+    pub outcomes: usize,
+    pub reasons: usize,
+    pub asked: usize,
+}
+
+impl Score {
+    pub fn points(&self) -> usize {
+        self.outcomes + self.reasons
+    }
+    pub fn possible(&self) -> usize {
+        self.asked * 2
+    }
+}
+
+// the interactive program for the repl, every snippet in a random order
+pub fn quiz(input: &mut dyn BufRead, out: &mut dyn Report) {
+    play(None, input, out);
+}
+
+// the snippets from one chapter (or all of them) in a random order
+pub fn play(chapter: Option<&str>, input: &mut dyn BufRead, out: &mut dyn Report) -> Score {
+    let mut questions = snippets::from_chapter(chapter);
+    let mut rng = rand::thread_rng();
+    questions.shuffle(&mut rng);
+    run(&questions, &mut rng, input, out)
+}
+
+// asks about each snippet in turn until they run out or the input ends
+pub fn run(
+    questions: &[&Snippet],
+    rng: &mut impl Rng,
+    input: &mut dyn BufRead,
+    out: &mut dyn Report,
+) -> Score {
+    say!(
+        out,
+        "For each snippet guess what happens and why. Type exit to stop."
+    );
+    let mut score = Score::default();
+    for (i, snippet) in questions.iter().enumerate() {
+        say!(out);
+        say!(
+            out,
+            "Question {}/{} (chapter {}, {})",
+            i + 1,
+            questions.len(),
+            snippet.chapter,
+            snippet.name
+        );
+        say!(out);
+        for line in snippet.code.lines() {
+            say!(out, "    {line}");
+        }
+        say!(out);
+        if ask(snippet, rng, input, out, &mut score).is_none() {
+            break;
+        }
+    }
+    say!(out);
+    say!(
+        out,
+        "You scored {}/{} ({} of {} outcomes and {} reasons right).",
+        score.points(),
+        score.possible(),
+        score.outcomes,
+        score.asked,
+        score.reasons
+    );
+    score
+}
+
+// None when the input ended part way through the question
+fn ask(
+    snippet: &Snippet,
+    rng: &mut impl Rng,
+    input: &mut dyn BufRead,
+    out: &mut dyn Report,
+    score: &mut Score,
+) -> Option<()> {
+    say!(
+        out,
+        "What happens when this runs?  [c]ompile error, [p]anic or [s]uccess"
+    );
+    let guess = loop {
+        let line = prompt_line(input, out, PROMPT)?;
+        match parse_outcome(&line) {
+            Some(guess) => break guess,
+            None => say!(out, "Please answer c, p or s."),
+        }
+    };
+    score.asked += 1;
+    if same_kind(guess, snippet.outcome) {
+        score.outcomes += 1;
+        say!(out, "Right, it {}.", snippet.outcome.describe());
+    } else {
+        say!(out, "Not quite, it {}.", snippet.outcome.describe());
+    }
+
+    let choices = choices(snippet.reason, rng);
+    say!(out, "Why?");
+    for (i, reason) in choices.iter().enumerate() {
+        say!(out, "  {}) {}", i + 1, reason.describe());
+    }
+    let answer = loop {
+        let line = prompt_line(input, out, PROMPT)?;
+        match line.parse::<usize>() {
+            Ok(n) if (1..=choices.len()).contains(&n) => break choices[n - 1],
+            _ => say!(out, "Please pick a number from 1 to {}.", choices.len()),
+        }
+    };
+    if answer == snippet.reason {
+        score.reasons += 1;
+        say!(out, "Right!");
+    } else {
+        say!(out, "Not quite, {}.", snippet.reason.describe());
+    }
+    say!(out, "{}", snippet.explanation);
+    Some(())
+}
+
+// the answer to "what happens", the error code isn't part of the guess
+fn parse_outcome(answer: &str) -> Option<Outcome> {
+    match answer.to_lowercase().as_str() {
+        "c" | "compile" | "compile error" | "error" => Some(Outcome::CompileError(None)),
+        "p" | "panic" | "panics" => Some(Outcome::Panic),
+        "s" | "success" | "runs" | "fine" => Some(Outcome::Compiles),
+        _ => None,
+    }
+}
+
+fn same_kind(a: Outcome, b: Outcome) -> bool {
+    matches!(
+        (a, b),
+        (Outcome::CompileError(_), Outcome::CompileError(_))
+            | (Outcome::Panic, Outcome::Panic)
+            | (Outcome::Compiles, Outcome::Compiles)
+    )
+}
+
+// the right reason and a few wrong ones, listed in the usual order so the position gives nothing away
+fn choices(right: Reason, rng: &mut impl Rng) -> Vec<Reason> {
+    let wrong: Vec<Reason> = Reason::ALL
+        .into_iter()
+        .filter(|reason| *reason != right)
+        .collect();
+    let mut picked: Vec<Reason> = wrong.choose_multiple(rng, CHOICES - 1).copied().collect();
+    picked.push(right);
+    picked.sort_by_key(|reason| Reason::ALL.iter().position(|r| r == reason));
+    picked
+}
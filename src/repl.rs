@@ -0,0 +1,166 @@
+// an interactive shell hosting the interactive exercises, so they can be entered and left again
+// without restarting the binary
+//
+//   rust_book> help
+//   rust_book> guess          (plays until you win or type exit)
+//   rust_book> history
+//   rust_book> !1             (runs the first command in the history again)
+//   rust_book> exit
+
+use std::io::BufRead;
+
+use crate::output::{say, Report};
+use crate::{chapter_eight_hw, chapter_three_hw, expr, guessing_game, overflow, quiz, units};
+
+// an exercise the shell can host, it reads from the shell's input until it is done
+pub struct Program {
+    pub name: &'static str,
+    pub summary: &'static str,
+    pub run: fn(&mut dyn BufRead, &mut dyn Report),
+}
+
+pub const PROGRAMS: &[Program] = &[
+    Program {
+        name: "guess",
+        summary: "the guessing game from chapter 2, with difficulty levels",
+        run: guessing_game::guessing_game,
+    },
+    Program {
+        name: "employees",
+        summary: "the employee directory from the chapter 8 homework",
+        run: chapter_eight_hw::employee_directory,
+    },
+    Program {
+        name: "pig-latin",
+        summary: "translate sentences into pig latin",
+        run: chapter_eight_hw::pig_latin_translator,
+    },
+    Program {
+        name: "temperature",
+        summary: "convert temperatures between ℃, ℉, K and °R",
+        run: chapter_three_hw::temperature_converter,
+    },
+    Program {
+        name: "convert",
+        summary: "convert lengths, masses, volumes, times and temperatures",
+        run: units::converter,
+    },
+    Program {
+        name: "eval",
+        summary: "work out arithmetic with rust's types, truncating division and all",
+        run: expr::calculator,
+    },
+    Program {
+        name: "overflow",
+        summary: "wrapping, checked, overflowing and saturating arithmetic for every integer type",
+        run: overflow::explorer,
+    },
+    Program {
+        name: "quiz",
+        summary: "does it compile, panic or run? guess for the examples from the notes",
+        run: quiz::quiz,
+    },
+];
+
+pub const PROMPT: &str = "rust_book> ";
+
+#[derive(Debug, Default)]
+pub struct Repl {
+    history: Vec<String>,
+}
+
+impl Repl {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // every command run so far, oldest first
+    pub fn history(&self) -> &[String] {
+        &self.history
+    }
+
+    // reads commands until exit or the end of the input
+    pub fn run(&mut self, input: &mut dyn BufRead, out: &mut dyn Report) {
+        say!(
+            out,
+            "Lets learn Rust! Type \"help\" to see what you can do."
+        );
+        while let Some(line) = prompt_line(input, out, PROMPT) {
+            if line.is_empty() {
+                continue;
+            }
+            let command = match self.expand(&line) {
+                Ok(command) => command,
+                Err(e) => {
+                    say!(out, "{e}");
+                    continue;
+                }
+            };
+            self.history.push(command.clone());
+            self.execute(&command, input, out);
+        }
+        say!(out, "Bye!");
+    }
+
+    // `!n` repeats the nth command from the history
+    fn expand(&self, line: &str) -> Result<String, String> {
+        match line.strip_prefix('!') {
+            Some(n) => n
+                .parse::<usize>()
+                .ok()
+                .and_then(|n| n.checked_sub(1))
+                .and_then(|i| self.history.get(i))
+                .cloned()
+                .ok_or_else(|| format!("no command {line} in the history")),
+            None => Ok(line.to_string()),
+        }
+    }
+
+    fn execute(&mut self, command: &str, input: &mut dyn BufRead, out: &mut dyn Report) {
+        match command {
+            "help" => help(out),
+            "history" => {
+                for (i, command) in self.history.iter().enumerate() {
+                    say!(out, "{:>4}  {command}", i + 1);
+                }
+            }
+            name => match PROGRAMS.iter().find(|program| program.name == name) {
+                Some(program) => {
+                    (program.run)(input, out);
+                    say!(out, "(back in the rust_book shell)");
+                }
+                None => say!(out, "unknown command \"{name}\", type \"help\" for a list"),
+            },
+        }
+    }
+}
+
+fn help(out: &mut dyn Report) {
+    say!(out, "commands:");
+    for program in PROGRAMS {
+        say!(out, "  {:<12} {}", program.name, program.summary);
+    }
+    say!(out, "  {:<12} list the commands run so far", "history");
+    say!(out, "  {:<12} run command n from the history again", "!n");
+    say!(out, "  {:<12} show this message", "help");
+    say!(
+        out,
+        "  {:<12} leave (also leaves any of the programs above)",
+        "exit"
+    );
+}
+
+// shows the prompt and reads one trimmed line
+// returns None at the end of the input or when the line is "exit" or "quit", which is how every
+// hosted program knows to hand control back
+pub fn prompt_line(input: &mut dyn BufRead, out: &mut dyn Report, prompt: &str) -> Option<String> {
+    out.prompt(prompt);
+    let mut line = String::new();
+    match input.read_line(&mut line) {
+        Ok(0) | Err(_) => None,
+        Ok(_) => match line.trim() {
+            "exit" | "quit" => None,
+            line => Some(line.to_string()),
+        },
+    }
+}
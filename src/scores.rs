@@ -0,0 +1,271 @@
+// high scores for the guessing game, every finished game is appended to scores.tsv in the data
+// directory, one tab separated line each:
+//   player  low  high  max attempts (- for no limit)  won|lost  attempts  milliseconds  unix time  feedback
+// attempts include what the hints cost, lines without the feedback column are classic games
+// games that were given up on aren't saved
+
+use std::{
+    collections::BTreeMap,
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    ops::RangeInclusive,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use crate::guessing_game::{Difficulty, End, Feedback, Settings, Summary};
+use crate::output::{say, Report};
+use crate::storage;
+
+pub const FILE_NAME: &str = "scores.tsv";
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Score {
+    pub player: String,
+    pub range: RangeInclusive<i32>,
+    pub max_attempts: Option<u32>,
+    pub feedback: Feedback,
+    pub won: bool,
+    pub attempts: u32,
+    pub duration: Duration,
+    pub timestamp: u64,
+}
+
+impl Score {
+    // None for a game that was given up on
+    pub fn new(settings: &Settings, summary: &Summary, player: &str) -> Option<Self> {
+        let won = match summary.end {
+            End::Won => true,
+            End::OutOfAttempts => false,
+            End::GaveUp => return None,
+        };
+        Some(Score {
+            // a tab or newline in the name would break the line up
+            player: player.replace(['\t', '\n', '\r'], " "),
+            range: settings.range.clone(),
+            max_attempts: settings.max_attempts,
+            feedback: settings.feedback,
+            won,
+            attempts: summary.attempts(),
+            duration: summary.duration,
+            timestamp: storage::now(),
+        })
+    }
+
+    // the preset the game was played on, or the range for custom games, and the feedback if it
+    // wasn't the usual too small / too big
+    pub fn difficulty(&self) -> String {
+        let settings = Settings {
+            range: self.range.clone(),
+            max_attempts: self.max_attempts,
+            feedback: Feedback::Classic,
+        };
+        let name = match Difficulty::ALL
+            .into_iter()
+            .find(|difficulty| difficulty.settings() == settings)
+        {
+            Some(difficulty) => difficulty.to_string(),
+            None => format!("custom ({settings})"),
+        };
+        match self.feedback {
+            Feedback::Classic => name,
+            feedback => format!("{name}, {feedback}"),
+        }
+    }
+
+    fn to_line(&self) -> String {
+        format!(
+            "{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}",
+            self.player,
+            self.range.start(),
+            self.range.end(),
+            self.max_attempts
+                .map_or(String::from("-"), |n| n.to_string()),
+            if self.won { "won" } else { "lost" },
+            self.attempts,
+            self.duration.as_millis(),
+            self.timestamp,
+            self.feedback
+        )
+    }
+
+    fn from_line(line: &str) -> Option<Self> {
+        let mut fields: Vec<&str> = line.split('\t').collect();
+        // scores saved before there were other kinds of feedback
+        if fields.len() == 8 {
+            fields.push("classic");
+        }
+        match fields[..] {
+            [player, low, high, max_attempts, won, attempts, millis, timestamp, feedback] => {
+                Some(Score {
+                    player: player.to_string(),
+                    range: low.parse().ok()?..=high.parse().ok()?,
+                    max_attempts: match max_attempts {
+                        "-" => None,
+                        n => Some(n.parse().ok()?),
+                    },
+                    feedback: feedback.parse().ok()?,
+                    won: match won {
+                        "won" => true,
+                        "lost" => false,
+                        _ => return None,
+                    },
+                    attempts: attempts.parse().ok()?,
+                    duration: Duration::from_millis(millis.parse().ok()?),
+                    timestamp: timestamp.parse().ok()?,
+                })
+            }
+            _ => None,
+        }
+    }
+}
+
+// what was read from the scores file
+#[derive(Debug, Default)]
+pub struct ScoreFile {
+    pub scores: Vec<Score>,
+    // lines that couldn't be read, e.g. after the file was edited by hand
+    pub skipped: usize,
+}
+
+pub fn default_path() -> io::Result<PathBuf> {
+    storage::data_file(FILE_NAME)
+}
+
+// a missing file has no scores yet, and damaged lines (or bytes that aren't utf-8) are skipped
+pub fn load(path: &Path) -> io::Result<ScoreFile> {
+    let bytes = match fs::read(path) {
+        Ok(bytes) => bytes,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(ScoreFile::default()),
+        Err(e) => return Err(e),
+    };
+    let mut file = ScoreFile::default();
+    for line in String::from_utf8_lossy(&bytes).lines() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        match Score::from_line(line) {
+            Some(score) => file.scores.push(score),
+            None => file.skipped += 1,
+        }
+    }
+    Ok(file)
+}
+
+pub fn record(path: &Path, score: &Score) -> io::Result<()> {
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{}", score.to_line())
+}
+
+// saves a finished game, games that were given up on are left out
+pub fn save(path: &Path, settings: &Settings, summary: &Summary, player: &str) -> io::Result<()> {
+    match Score::new(settings, summary, player) {
+        Some(score) => record(path, &score),
+        None => Ok(()),
+    }
+}
+
+// a player's results on one difficulty
+#[derive(Debug, Clone, PartialEq)]
+pub struct Standing {
+    pub player: String,
+    pub games: usize,
+    pub wins: usize,
+    // fewest attempts in a win, and how long that game took
+    pub best: Option<(u32, Duration)>,
+}
+
+// players on each difficulty, best first: fewest attempts, then fastest, then most wins
+// players without a win come last
+pub fn leaderboard(scores: &[Score]) -> BTreeMap<String, Vec<Standing>> {
+    let mut players: BTreeMap<(String, String), Standing> = BTreeMap::new();
+    for score in scores {
+        let standing = players
+            .entry((score.difficulty(), score.player.clone()))
+            .or_insert_with(|| Standing {
+                player: score.player.clone(),
+                games: 0,
+                wins: 0,
+                best: None,
+            });
+        standing.games += 1;
+        if score.won {
+            standing.wins += 1;
+            let this = (score.attempts, score.duration);
+            if standing.best.is_none_or(|best| this < best) {
+                standing.best = Some(this);
+            }
+        }
+    }
+
+    let mut boards: BTreeMap<String, Vec<Standing>> = BTreeMap::new();
+    for ((difficulty, _), standing) in players {
+        boards.entry(difficulty).or_default().push(standing);
+    }
+    for standings in boards.values_mut() {
+        standings.sort_by(|a, b| match (a.best, b.best) {
+            (Some(a_best), Some(b_best)) => a_best.cmp(&b_best).then(b.wins.cmp(&a.wins)),
+            (Some(_), None) => std::cmp::Ordering::Less,
+            (None, Some(_)) => std::cmp::Ordering::Greater,
+            (None, None) => b.games.cmp(&a.games),
+        });
+    }
+    boards
+}
+
+// the presets in order, then any custom games
+fn board_order(name: &str) -> (usize, &str) {
+    let preset = Difficulty::ALL
+        .iter()
+        .position(|difficulty| difficulty.to_string() == name);
+    (preset.unwrap_or(Difficulty::ALL.len()), name)
+}
+
+// the leaderboard for every difficulty, or only the one asked for
+pub fn print(file: &ScoreFile, difficulty: Option<Difficulty>, out: &mut dyn Report) {
+    let boards = leaderboard(&file.scores);
+    let mut names: Vec<&String> = boards
+        .keys()
+        .filter(|name| difficulty.is_none_or(|d| d.to_string() == **name))
+        .collect();
+    names.sort_by_key(|name| board_order(name));
+
+    if names.is_empty() {
+        say!(out, "No games played yet, try `rust_book play`.");
+    }
+    for name in names {
+        out.heading(&format!("Leaderboard: {name}"));
+        say!(
+            out,
+            "{:>3}  {:<16} {:>5} {:>8} {:>7}",
+            "#",
+            "player",
+            "best",
+            "time",
+            "won"
+        );
+        for (i, standing) in boards[name].iter().enumerate() {
+            let (best, time) = match standing.best {
+                Some((attempts, duration)) => (
+                    attempts.to_string(),
+                    format!("{:.1}s", duration.as_secs_f64()),
+                ),
+                None => (String::from("-"), String::from("-")),
+            };
+            say!(
+                out,
+                "{:>3}  {:<16} {best:>5} {time:>8} {:>7}",
+                i + 1,
+                standing.player,
+                format!("{}/{}", standing.wins, standing.games)
+            );
+        }
+    }
+    if file.skipped > 0 {
+        say!(
+            out,
+            "({} damaged lines in the scores file were skipped)",
+            file.skipped
+        );
+    }
+}
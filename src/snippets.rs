@@ -0,0 +1,411 @@
+// the "this will not compile" and "this will panic" examples from the notes, kept as data so the quiz
+// can ask about them (and so they can be checked against the real compiler)
+// every snippet is a whole program so it can be compiled on its own
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Outcome {
+    // the error code rustc reports, None for errors without one (mostly syntax errors)
+    CompileError(Option<&'static str>),
+    // compiles but panics when it runs
+    Panic,
+    // compiles and runs to the end
+    Compiles,
+}
+
+impl Outcome {
+    pub fn describe(&self) -> String {
+        match self {
+            Outcome::CompileError(Some(code)) => format!("does not compile (error[{code}])"),
+            Outcome::CompileError(None) => String::from("does not compile"),
+            Outcome::Panic => String::from("compiles but panics at runtime"),
+            Outcome::Compiles => String::from("compiles and runs fine"),
+        }
+    }
+}
+
+// why a snippet does what it does, the quiz offers a few of these as answers
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Reason {
+    Immutable,
+    TypeMismatch,
+    LetIsAStatement,
+    Moved,
+    TwoMutableBorrows,
+    MutableWhileBorrowed,
+    Dangling,
+    NotImplemented,
+    OutOfBounds,
+    CharBoundary,
+    ExplicitPanic,
+    Fine,
+}
+
+impl Reason {
+    // the order the answers are listed in
+    pub const ALL: [Reason; 12] = [
+        Reason::Immutable,
+        Reason::TypeMismatch,
+        Reason::LetIsAStatement,
+        Reason::Moved,
+        Reason::TwoMutableBorrows,
+        Reason::MutableWhileBorrowed,
+        Reason::Dangling,
+        Reason::NotImplemented,
+        Reason::OutOfBounds,
+        Reason::CharBoundary,
+        Reason::ExplicitPanic,
+        Reason::Fine,
+    ];
+
+    pub fn describe(&self) -> &'static str {
+        match self {
+            Reason::Immutable => "variables are immutable unless they are declared with mut",
+            Reason::TypeMismatch => "a variable (or an if expression) can only have one type",
+            Reason::LetIsAStatement => "let is a statement, it doesn't return a value",
+            Reason::Moved => "the value was moved, the old variable can't be used any more",
+            Reason::TwoMutableBorrows => "there can only be one mutable reference at a time",
+            Reason::MutableWhileBorrowed => {
+                "it can't be borrowed as mutable while an immutable reference is still used"
+            }
+            Reason::Dangling => "the reference would outlive the value it points to",
+            Reason::NotImplemented => "the type doesn't support that operation (indexing, +, ...)",
+            Reason::OutOfBounds => "the index is past the end, which is only checked at runtime",
+            Reason::CharBoundary => "the range doesn't end on a character boundary",
+            Reason::ExplicitPanic => "panic! is called directly",
+            Reason::Fine => "nothing, the ownership and borrowing rules are all followed",
+        }
+    }
+}
+
+pub struct Snippet {
+    // unique within the chapter, the notes point at them as e.g. "quiz snippet 4 dangling-reference"
+    pub name: &'static str,
+    // id of the chapter the snippet comes from, as used by `rust_book run`
+    pub chapter: &'static str,
+    pub code: &'static str,
+    pub outcome: Outcome,
+    pub reason: Reason,
+    pub explanation: &'static str,
+}
+
+pub const SNIPPETS: &[Snippet] = &[
+    Snippet {
+        name: "assign-immutable",
+        chapter: "3",
+        code: "fn main() {
+    let x = 5;
+    x = 6;
+}",
+        outcome: Outcome::CompileError(Some("E0384")),
+        reason: Reason::Immutable,
+        explanation:
+            "x needs to be `let mut x` before it can be assigned to again, this is caught by \
+            the compiler and never gets as far as panicking.",
+    },
+    Snippet {
+        name: "shadowing",
+        chapter: "3",
+        code: "fn main() {
+    let spaces = \"   \";
+    let spaces = spaces.len();
+    println!(\"{spaces}\");
+}",
+        outcome: Outcome::Compiles,
+        reason: Reason::Fine,
+        explanation:
+            "the second let makes a new variable that happens to have the same name, so it \
+            can have a different type.",
+    },
+    Snippet {
+        name: "mutate-to-another-type",
+        chapter: "3",
+        code: "fn main() {
+    let mut spaces = \"   \";
+    spaces = spaces.len();
+}",
+        outcome: Outcome::CompileError(Some("E0308")),
+        reason: Reason::TypeMismatch,
+        explanation:
+            "mut lets the value change but not the type, spaces is a &str so it can't hold \
+            a usize. shadowing with a second let is the way to do this.",
+    },
+    Snippet {
+        name: "let-in-let",
+        chapter: "3",
+        code: "fn main() {
+    let x = (let y = 6);
+}",
+        outcome: Outcome::CompileError(None),
+        reason: Reason::LetIsAStatement,
+        explanation: "statements don't return values, so there is nothing for x to bind to. rustc \
+                      stops while parsing: expected expression, found `let` statement.",
+    },
+    Snippet {
+        name: "if-else-types",
+        chapter: "3",
+        code: "fn main() {
+    let condition = true;
+    let number = if condition { 5 } else { \"six\" };
+}",
+        outcome: Outcome::CompileError(Some("E0308")),
+        reason: Reason::TypeMismatch,
+        explanation: "both arms of the if are values for number, and number needs a single type \
+                      known at compile time.",
+    },
+    Snippet {
+        name: "array-past-the-end",
+        chapter: "3",
+        code: "fn main() {
+    let a = [1, 2, 3, 4, 5];
+    for i in 0..=6 {
+        println!(\"the value is: {}\", a[i]);
+    }
+}",
+        outcome: Outcome::Panic,
+        reason: Reason::OutOfBounds,
+        explanation: "the first five values are printed, then a[5] panics with 'index out of \
+                      bounds: the len is 5 but the index is 5'.",
+    },
+    Snippet {
+        name: "use-after-move",
+        chapter: "4",
+        code: "fn main() {
+    let s1 = String::from(\"hello\");
+    let s2 = s1;
+    println!(\"s1 = {}\", s1);
+}",
+        outcome: Outcome::CompileError(Some("E0382")),
+        reason: Reason::Moved,
+        explanation: "let s2 = s1 moves the String into s2 so only one of them frees the memory. \
+                      s1.clone() would make a deep copy instead.",
+    },
+    Snippet {
+        name: "clone",
+        chapter: "4",
+        code: "fn main() {
+    let s1 = String::from(\"hello\");
+    let s2 = s1.clone();
+    println!(\"s1 = {}, s2 = {}\", s1, s2);
+}",
+        outcome: Outcome::Compiles,
+        reason: Reason::Fine,
+        explanation: "clone copies the heap data too, so s1 and s2 own separate Strings.",
+    },
+    Snippet {
+        name: "double-mutable-borrow",
+        chapter: "4",
+        code: "fn main() {
+    let mut s = String::from(\"hello\");
+    let r1 = &mut s;
+    let r2 = &mut s;
+    println!(\"{}{}\", r1, r2);
+}",
+        outcome: Outcome::CompileError(Some("E0499")),
+        reason: Reason::TwoMutableBorrows,
+        explanation: "r1 is still used in the println! when r2 is created, two mutable references \
+                      to s at the same time could cause a data race.",
+    },
+    Snippet {
+        name: "mutable-borrows-in-scopes",
+        chapter: "4",
+        code: "fn main() {
+    let mut s = String::from(\"hello\");
+    {
+        let r1 = &mut s;
+        println!(\"{}\", r1);
+    }
+    let r2 = &mut s;
+    println!(\"{}\", r2);
+}",
+        outcome: Outcome::Compiles,
+        reason: Reason::Fine,
+        explanation:
+            "r1 goes out of scope at the closing brace, so there is only ever one mutable \
+            reference at a time.",
+    },
+    Snippet {
+        name: "mutable-and-immutable-borrow",
+        chapter: "4",
+        code: "fn main() {
+    let mut s = String::from(\"hello\");
+    let r1 = &s;
+    let r2 = &s;
+    let r3 = &mut s;
+    println!(\"{}{}{}\", r1, r2, r3);
+}",
+        outcome: Outcome::CompileError(Some("E0502")),
+        reason: Reason::MutableWhileBorrowed,
+        explanation: "r1 and r2 don't expect the String to change under them, and they are still \
+                      used after r3 is created.",
+    },
+    Snippet {
+        name: "borrows-end-at-last-use",
+        chapter: "4",
+        code: "fn main() {
+    let mut s = String::from(\"hello\");
+    let r1 = &s;
+    let r2 = &s;
+    println!(\"{} and {}\", r1, r2);
+    let r3 = &mut s;
+    println!(\"{}\", r3);
+}",
+        outcome: Outcome::Compiles,
+        reason: Reason::Fine,
+        explanation:
+            "a reference's scope ends the last time it is used, r1 and r2 are done by the \
+            time r3 is created.",
+    },
+    Snippet {
+        name: "dangling-reference",
+        chapter: "4",
+        code: "fn dangle() -> &String {
+    let s = String::from(\"hello\");
+    &s
+}
+
+fn main() {
+    let reference_to_nothing = dangle();
+}",
+        outcome: Outcome::CompileError(Some("E0106")),
+        reason: Reason::Dangling,
+        explanation: "s is dropped when dangle returns, so the reference would point at freed \
+                      memory. rustc asks for a lifetime, returning the String itself fixes it.",
+    },
+    Snippet {
+        name: "clear-while-borrowed",
+        chapter: "4",
+        code: "fn first_word(s: &String) -> &str {
+    let bytes = s.as_bytes();
+    for (i, &item) in bytes.iter().enumerate() {
+        if item == b' ' {
+            return &s[0..i];
+        }
+    }
+    &s[..]
+}
+
+fn main() {
+    let mut s = String::from(\"hello world\");
+    let word = first_word(&s);
+    s.clear();
+    println!(\"the first word is: {}\", word);
+}",
+        outcome: Outcome::CompileError(Some("E0502")),
+        reason: Reason::MutableWhileBorrowed,
+        explanation:
+            "clear needs a mutable reference to s, but word is a slice of s that is still \
+            used afterwards.",
+    },
+    Snippet {
+        name: "add-option",
+        chapter: "6",
+        code: "fn main() {
+    let x: i8 = 5;
+    let y: Option<i8> = Some(5);
+    let sum = x + y;
+}",
+        outcome: Outcome::CompileError(Some("E0277")),
+        reason: Reason::NotImplemented,
+        explanation: "an Option<i8> might be None, so it has to be matched (or unwrapped) into an \
+                      i8 before it can be added.",
+    },
+    Snippet {
+        name: "index-past-the-end",
+        chapter: "8",
+        code: "fn main() {
+    let v = vec![1, 2, 3, 4, 5];
+    let does_not_exist = &v[100];
+}",
+        outcome: Outcome::Panic,
+        reason: Reason::OutOfBounds,
+        explanation: "a vector's length is only known at runtime, so indexing past the end panics.",
+    },
+    Snippet {
+        name: "get-past-the-end",
+        chapter: "8",
+        code: "fn main() {
+    let v = vec![1, 2, 3, 4, 5];
+    let does_not_exist = v.get(100);
+    println!(\"{:?}\", does_not_exist);
+}",
+        outcome: Outcome::Compiles,
+        reason: Reason::Fine,
+        explanation: "get returns an Option, so a missing element is None instead of a panic.",
+    },
+    Snippet {
+        name: "push-while-borrowed",
+        chapter: "8",
+        code: "fn main() {
+    let mut v = vec![1, 2, 3, 4, 5];
+    let first = &v[0];
+    v.push(6);
+    println!(\"The first element is: {first}\");
+}",
+        outcome: Outcome::CompileError(Some("E0502")),
+        reason: Reason::MutableWhileBorrowed,
+        explanation:
+            "push might move the elements to a bigger allocation, which would leave first \
+            pointing at freed memory.",
+    },
+    Snippet {
+        name: "index-string",
+        chapter: "8",
+        code: "fn main() {
+    let s1 = String::from(\"hello\");
+    let h = s1[0];
+}",
+        outcome: Outcome::CompileError(Some("E0277")),
+        reason: Reason::NotImplemented,
+        explanation: "Strings are UTF-8 bytes underneath and a byte isn't always a character, so \
+                      String doesn't implement indexing by integer.",
+    },
+    Snippet {
+        name: "index-str",
+        chapter: "8",
+        code: "fn main() {
+    let hello = \"Здравствуйте\";
+    let answer = &hello[0];
+}",
+        outcome: Outcome::CompileError(Some("E0277")),
+        reason: Reason::NotImplemented,
+        explanation: "the same goes for &str, З is two bytes so hello[0] couldn't be a whole \
+                      character.",
+    },
+    Snippet {
+        name: "slice-mid-character",
+        chapter: "8",
+        code: "fn main() {
+    let hello = \"Здравствуйте\";
+    let s = &hello[0..1];
+}",
+        outcome: Outcome::Panic,
+        reason: Reason::CharBoundary,
+        explanation:
+            "slicing with a range compiles, but byte 1 is in the middle of З so it panics \
+            at runtime.",
+    },
+    Snippet {
+        name: "panic-at-the-disco",
+        chapter: "9",
+        code: "fn main() {
+    panic!(\"crash and burn\");
+}",
+        outcome: Outcome::Panic,
+        reason: Reason::ExplicitPanic,
+        explanation: "panic! prints the message, unwinds the stack and exits with an error.",
+    },
+];
+
+pub fn find(chapter: &str, name: &str) -> Option<&'static Snippet> {
+    SNIPPETS
+        .iter()
+        .find(|snippet| snippet.chapter == chapter && snippet.name == name)
+}
+
+// snippets from one chapter, or all of them
+pub fn from_chapter(chapter: Option<&str>) -> Vec<&'static Snippet> {
+    SNIPPETS
+        .iter()
+        .filter(|snippet| chapter.is_none_or(|id| snippet.chapter == id))
+        .collect()
+}
@@ -0,0 +1,256 @@
+// the computer playing the guessing game against itself, to compare ways of picking the next guess
+// every strategy gets the same secrets (from a seeded rng) and the too small / too big feedback,
+// which narrows down where the secret can be. halving that each time is why binary search needs at
+// most log2(n) + 1 guesses
+//
+//   rust_book solve --games 10000 --range 1..=1000
+
+use std::{collections::BTreeMap, ops::RangeInclusive};
+
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use crate::guessing_game::{Feedback, GuessingGame, Outcome, Settings};
+use crate::output::{say, Report};
+
+pub trait Strategy {
+    fn name(&self) -> &'static str;
+    fn describe(&self) -> &'static str;
+    // the next guess, the secret is somewhere in low..=high
+    fn guess(&mut self, low: i32, high: i32, rng: &mut StdRng) -> i32;
+    // told the secret after every game, for strategies that learn
+    fn learn(&mut self, _secret: i32) {}
+}
+
+pub struct Binary;
+impl Strategy for Binary {
+    fn name(&self) -> &'static str {
+        "binary"
+    }
+    fn describe(&self) -> &'static str {
+        "guess the middle of what's left"
+    }
+    fn guess(&mut self, low: i32, high: i32, _rng: &mut StdRng) -> i32 {
+        // in i64 so neither low + high nor high - low can overflow, the middle always fits back in i32
+        ((i64::from(low) + i64::from(high)).div_euclid(2)) as i32
+    }
+}
+
+pub struct Random;
+impl Strategy for Random {
+    fn name(&self) -> &'static str {
+        "random"
+    }
+    fn describe(&self) -> &'static str {
+        "guess anything that's left"
+    }
+    fn guess(&mut self, low: i32, high: i32, rng: &mut StdRng) -> i32 {
+        rng.gen_range(low..=high)
+    }
+}
+
+pub struct Linear;
+impl Strategy for Linear {
+    fn name(&self) -> &'static str {
+        "linear"
+    }
+    fn describe(&self) -> &'static str {
+        "count up from the bottom"
+    }
+    fn guess(&mut self, low: i32, _high: i32, _rng: &mut StdRng) -> i32 {
+        low
+    }
+}
+
+// guesses where earlier secrets were, which only pays off when the secrets aren't spread evenly
+#[derive(Default)]
+pub struct LearnedMedian {
+    secrets: Vec<i32>,
+}
+impl Strategy for LearnedMedian {
+    fn name(&self) -> &'static str {
+        "learned-median"
+    }
+    fn describe(&self) -> &'static str {
+        "guess the median of the earlier secrets that are still possible"
+    }
+    fn guess(&mut self, low: i32, high: i32, rng: &mut StdRng) -> i32 {
+        // the secrets are sorted, so the ones in low..=high are next to each other
+        let from = self.secrets.partition_point(|s| *s < low);
+        let to = self.secrets.partition_point(|s| *s <= high);
+        match &self.secrets[from..to] {
+            [] => Binary.guess(low, high, rng),
+            possible => possible[possible.len() / 2],
+        }
+    }
+    fn learn(&mut self, secret: i32) {
+        // kept sorted so the median is the middle one
+        let i = self.secrets.partition_point(|s| *s < secret);
+        self.secrets.insert(i, secret);
+    }
+}
+
+pub fn strategies() -> Vec<Box<dyn Strategy>> {
+    vec![
+        Box::new(Binary),
+        Box::new(Random),
+        Box::new(Linear),
+        Box::new(LearnedMedian::default()),
+    ]
+}
+
+pub fn strategy(name: &str) -> Option<Box<dyn Strategy>> {
+    strategies()
+        .into_iter()
+        .find(|strategy| strategy.name() == name)
+}
+
+// how many guesses a strategy needed in each game
+#[derive(Debug, Clone, PartialEq)]
+pub struct Stats {
+    pub strategy: &'static str,
+    pub how: &'static str,
+    pub attempts: Vec<u32>,
+}
+
+impl Stats {
+    pub fn mean(&self) -> f64 {
+        let total: u64 = self.attempts.iter().map(|n| u64::from(*n)).sum();
+        total as f64 / self.attempts.len().max(1) as f64
+    }
+    pub fn worst(&self) -> u32 {
+        self.attempts.iter().copied().max().unwrap_or(0)
+    }
+    pub fn best(&self) -> u32 {
+        self.attempts.iter().copied().min().unwrap_or(0)
+    }
+    // number of games won in each number of guesses
+    pub fn distribution(&self) -> BTreeMap<u32, usize> {
+        let mut counts = BTreeMap::new();
+        for n in &self.attempts {
+            *counts.entry(*n).or_insert(0) += 1;
+        }
+        counts
+    }
+}
+
+// one game with no attempt limit, returns how many guesses it took
+pub fn solve(game: &mut GuessingGame, strategy: &mut dyn Strategy, rng: &mut StdRng) -> u32 {
+    let (mut low, mut high) = (*game.settings().range.start(), *game.settings().range.end());
+    loop {
+        let guess = strategy.guess(low, high, rng).clamp(low, high);
+        match game.guess(guess) {
+            Outcome::TooSmall => low = guess + 1,
+            Outcome::TooBig => high = guess - 1,
+            // the solver only plays with classic feedback
+            _ => break,
+        }
+    }
+    strategy.learn(game.secret());
+    game.guesses().len() as u32
+}
+
+// plays `games` games with the same seed, so every strategy gets the same secrets
+pub fn compare(
+    strategy: &mut dyn Strategy,
+    range: RangeInclusive<i32>,
+    games: usize,
+    seed: u64,
+) -> Stats {
+    let mut secrets = StdRng::seed_from_u64(seed);
+    let mut choices = StdRng::seed_from_u64(seed.wrapping_add(1));
+    let settings = Settings {
+        range,
+        max_attempts: None,
+        feedback: Feedback::Classic,
+    };
+    let attempts = (0..games)
+        .map(|_| {
+            let mut game = GuessingGame::new(settings.clone(), &mut secrets);
+            solve(&mut game, strategy, &mut choices)
+        })
+        .collect();
+    Stats {
+        strategy: strategy.name(),
+        how: strategy.describe(),
+        attempts,
+    }
+}
+
+// the fewest guesses that always find a secret in a range this big, log2(size) rounded down + 1
+// (7 for 1..=100)
+pub fn binary_bound(range: &RangeInclusive<i32>) -> u32 {
+    let size = (i64::from(*range.end()) - i64::from(*range.start()) + 1).max(1) as u64;
+    u64::BITS - size.leading_zeros()
+}
+
+const BAR_WIDTH: usize = 40;
+
+pub fn report(stats: &[Stats], range: &RangeInclusive<i32>, out: &mut dyn Report) {
+    let games = stats.first().map_or(0, |stats| stats.attempts.len());
+    out.heading(&format!(
+        "{games} games guessing a number from {} to {}",
+        range.start(),
+        range.end()
+    ));
+    say!(
+        out,
+        "{:<14} {:>8} {:>6} {:>6}  how",
+        "strategy",
+        "mean",
+        "best",
+        "worst"
+    );
+    for stats in stats {
+        say!(
+            out,
+            "{:<14} {:>8.2} {:>6} {:>6}  {}",
+            stats.strategy,
+            stats.mean(),
+            stats.best(),
+            stats.worst(),
+            stats.how
+        );
+    }
+    say!(
+        out,
+        "binary search never needs more than {} guesses here",
+        binary_bound(range)
+    );
+
+    for stats in stats {
+        out.heading(&format!("{} (guesses: games)", stats.strategy));
+        // long tails (linear on a big range) are grouped so the chart stays readable
+        let rows = bucket(&stats.distribution(), 20);
+        let most = rows.iter().map(|(_, count)| *count).max().unwrap_or(1);
+        let width = rows.iter().map(|(label, _)| label.len()).max().unwrap_or(0);
+        for (label, count) in rows {
+            let bar = "#".repeat((count * BAR_WIDTH).div_ceil(most.max(1)));
+            say!(out, "{label:>width$} | {bar} {count}");
+        }
+    }
+}
+
+// at most `max` rows, each covering the same number of attempt counts
+fn bucket(distribution: &BTreeMap<u32, usize>, max: u32) -> Vec<(String, usize)> {
+    let (Some(first), Some(last)) = (
+        distribution.keys().next().copied(),
+        distribution.keys().last().copied(),
+    ) else {
+        return Vec::new();
+    };
+    let width = (last - first + 1).div_ceil(max);
+    let mut rows = Vec::new();
+    let mut start = first;
+    while start <= last {
+        let end = start + width - 1;
+        let count = distribution.range(start..=end).map(|(_, n)| n).sum();
+        let label = if width == 1 {
+            start.to_string()
+        } else {
+            format!("{start}-{end}")
+        };
+        rows.push((label, count));
+        start = end + 1;
+    }
+    rows
+}
@@ -0,0 +1,364 @@
+// cumulative songs, where every verse brings in something new and then goes back through
+// everything that came before it. the words live in songs/*.song so adding a song doesn't mean
+// touching any code, and they come out as plain text, markdown or html
+//
+//   rust_book sing twelve-days
+//   rust_book sing old-macdonald --format html --output farm.html
+//   rust_book sing my_song.song
+//
+// a .song file is `key: value` lines, blank lines and lines starting with # are skipped
+//
+//   title: The House That Jack Built
+//   fields: thing | link              the names of the parts of each item
+//   verse: This is {thing}            the start of every verse, with the new item's fields
+//   each: {link}                      the lines for every item so far, newest first
+//   first: ...                        (optional) used instead of `each` for the first item
+//   ending: ...                       (optional) the end of every verse
+//   item: the rat | That ate the malt one per item in the order they're sung, fields split by |
+//
+// verse, each, first and ending can be given more than once for more than one line. besides the
+// fields the lines can use {n} for the verse (or the item in `each` and `first`), {words} for it
+// spelled out, {nth} for 1st, 2nd, ... and {ordinal} for first, second, ... a line that comes out
+// empty is left out
+
+use std::{fmt, str::FromStr};
+
+use crate::numbers;
+
+// the songs that come with the binary, the name and the file
+pub const SONGS: &[(&str, &str)] = &[
+    ("twelve-days", include_str!("../songs/twelve_days.song")),
+    ("old-macdonald", include_str!("../songs/old_macdonald.song")),
+    (
+        "house-that-jack-built",
+        include_str!("../songs/house_that_jack_built.song"),
+    ),
+];
+
+// one of the songs above, the built in files are checked by the tests so this doesn't fail
+pub fn builtin(name: &str) -> Option<Song> {
+    SONGS
+        .iter()
+        .find(|(song, _)| song.eq_ignore_ascii_case(name))
+        .map(|(_, text)| text.parse().expect("the built in songs parse"))
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SongError {
+    // a line that isn't `key: value` or has a key we don't know
+    Syntax {
+        line: usize,
+        text: String,
+    },
+    // title, fields, each and at least one item have to be there
+    Missing(&'static str),
+    UnknownField {
+        line: usize,
+        name: String,
+    },
+    // an item with more or fewer parts than there are fields
+    WrongFields {
+        line: usize,
+        expected: usize,
+        found: usize,
+    },
+}
+
+impl fmt::Display for SongError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SongError::Syntax { line, text } => {
+                write!(f, "line {line}: expected `key: value`, got \"{text}\"")
+            }
+            SongError::Missing(key) => write!(f, "the song has no {key}"),
+            SongError::UnknownField { line, name } => {
+                write!(f, "line {line}: there's no field called {{{name}}}")
+            }
+            SongError::WrongFields {
+                line,
+                expected,
+                found,
+            } => write!(
+                f,
+                "line {line}: expected {expected} fields separated by |, found {found}"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for SongError {}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Style {
+    Plain,
+    Markdown,
+    Html,
+}
+
+impl FromStr for Style {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "plain" | "text" | "txt" => Ok(Style::Plain),
+            "markdown" | "md" => Ok(Style::Markdown),
+            "html" => Ok(Style::Html),
+            _ => Err(format!(
+                "unknown song format \"{s}\", expected plain, markdown or html"
+            )),
+        }
+    }
+}
+
+impl fmt::Display for Style {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Style::Plain => write!(f, "plain"),
+            Style::Markdown => write!(f, "markdown"),
+            Style::Html => write!(f, "html"),
+        }
+    }
+}
+
+// a line of a song with the gaps to fill in, worked out when the file is read so a typo in a field
+// name is found straight away rather than halfway through singing
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Template(Vec<Piece>);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Piece {
+    Text(String),
+    Field(usize),
+    Number,
+    Words,
+    Nth,
+    Ordinal,
+}
+
+impl Template {
+    fn parse(text: &str, fields: &[String], line: usize) -> Result<Self, SongError> {
+        let mut pieces = Vec::new();
+        let mut rest = text;
+        while let Some(open) = rest.find('{') {
+            let Some(close) = rest[open..].find('}').map(|close| open + close) else {
+                break;
+            };
+            if open > 0 {
+                pieces.push(Piece::Text(rest[..open].to_string()));
+            }
+            let name = &rest[open + 1..close];
+            pieces.push(match name {
+                "n" => Piece::Number,
+                "words" => Piece::Words,
+                "nth" => Piece::Nth,
+                "ordinal" => Piece::Ordinal,
+                _ => Piece::Field(fields.iter().position(|field| field == name).ok_or_else(
+                    || SongError::UnknownField {
+                        line,
+                        name: name.to_string(),
+                    },
+                )?),
+            });
+            rest = &rest[close + 1..];
+        }
+        // an unmatched { is just a {
+        if !rest.is_empty() {
+            pieces.push(Piece::Text(rest.to_string()));
+        }
+        Ok(Template(pieces))
+    }
+
+    fn fill(&self, n: usize, item: &[String]) -> String {
+        self.0
+            .iter()
+            .map(|piece| match piece {
+                Piece::Text(text) => text.clone(),
+                Piece::Field(i) => item[*i].clone(),
+                Piece::Number => n.to_string(),
+                Piece::Words => numbers::cardinal_words(n),
+                Piece::Nth => numbers::ordinal(n),
+                Piece::Ordinal => numbers::ordinal_words(n),
+            })
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Song {
+    pub title: String,
+    fields: Vec<String>,
+    verse: Vec<Template>,
+    each: Vec<Template>,
+    first: Vec<Template>,
+    ending: Vec<Template>,
+    items: Vec<Vec<String>>,
+}
+
+impl FromStr for Song {
+    type Err = SongError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut lines = Vec::new();
+        for (i, line) in s.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            match line.split_once(':') {
+                Some((key, value))
+                    if [
+                        "title", "fields", "verse", "each", "first", "ending", "item",
+                    ]
+                    .contains(&key.trim()) =>
+                {
+                    lines.push((i + 1, key.trim(), value.trim()))
+                }
+                _ => {
+                    return Err(SongError::Syntax {
+                        line: i + 1,
+                        text: line.to_string(),
+                    })
+                }
+            }
+        }
+        let values = |key: &'static str| lines.iter().filter(move |(_, k, _)| *k == key);
+        let split = |value: &str| {
+            value
+                .split('|')
+                .map(|part| part.trim().to_string())
+                .collect()
+        };
+
+        let title = values("title")
+            .next_back()
+            .ok_or(SongError::Missing("title"))?
+            .2;
+        let fields: Vec<String> = split(
+            values("fields")
+                .next_back()
+                .ok_or(SongError::Missing("fields"))?
+                .2,
+        );
+        let templates = |key: &'static str| {
+            values(key)
+                .map(|(line, _, value)| Template::parse(value, &fields, *line))
+                .collect::<Result<Vec<_>, _>>()
+        };
+        let each = templates("each")?;
+        if each.is_empty() {
+            return Err(SongError::Missing("each"));
+        }
+        let items = values("item")
+            .map(|(line, _, value)| {
+                let item: Vec<String> = split(value);
+                if item.len() != fields.len() {
+                    return Err(SongError::WrongFields {
+                        line: *line,
+                        expected: fields.len(),
+                        found: item.len(),
+                    });
+                }
+                Ok(item)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        if items.is_empty() {
+            return Err(SongError::Missing("items"));
+        }
+        Ok(Song {
+            title: title.to_string(),
+            verse: templates("verse")?,
+            first: templates("first")?,
+            ending: templates("ending")?,
+            fields,
+            each,
+            items,
+        })
+    }
+}
+
+impl Song {
+    // one verse for every item
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    // the lines of verse n, counting from 1
+    pub fn verse(&self, n: usize) -> Vec<String> {
+        let newest = &self.items[n - 1];
+        let mut lines: Vec<String> = self
+            .verse
+            .iter()
+            .map(|template| template.fill(n, newest))
+            .collect();
+        for (i, item) in self.items[..n].iter().enumerate().rev() {
+            let templates = match i {
+                0 if !self.first.is_empty() => &self.first,
+                _ => &self.each,
+            };
+            lines.extend(templates.iter().map(|template| template.fill(i + 1, item)));
+        }
+        lines.extend(self.ending.iter().map(|template| template.fill(n, newest)));
+        lines.retain(|line| !line.trim().is_empty());
+        lines
+    }
+
+    pub fn verses(&self) -> Vec<Vec<String>> {
+        (1..=self.len()).map(|n| self.verse(n)).collect()
+    }
+
+    // the title and then the verses with a gap between them
+    pub fn render(&self, style: Style) -> String {
+        let verses = self.verses();
+        match style {
+            Style::Plain => {
+                let verses: Vec<String> = verses.iter().map(|verse| verse.join("\n")).collect();
+                format!("{}\n\n{}\n", self.title, verses.join("\n\n"))
+            }
+            // a \ at the end of a line keeps the verse's line breaks
+            Style::Markdown => {
+                let verses: Vec<String> = verses
+                    .iter()
+                    .map(|verse| {
+                        let lines: Vec<String> =
+                            verse.iter().map(|line| escape_markdown(line)).collect();
+                        lines.join("\\\n")
+                    })
+                    .collect();
+                format!(
+                    "# {}\n\n{}\n",
+                    escape_markdown(&self.title),
+                    verses.join("\n\n")
+                )
+            }
+            Style::Html => {
+                let mut html = format!("<h1>{}</h1>\n", escape_html(&self.title));
+                for verse in verses {
+                    let lines: Vec<String> = verse.iter().map(|line| escape_html(line)).collect();
+                    html.push_str(&format!("<p>\n{}\n</p>\n", lines.join("<br>\n")));
+                }
+                html
+            }
+        }
+    }
+}
+
+// so a * or _ in the words doesn't turn into emphasis
+fn escape_markdown(text: &str) -> String {
+    let mut escaped = String::new();
+    for c in text.chars() {
+        if "\\`*_[]<>#|".contains(c) {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
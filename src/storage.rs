@@ -0,0 +1,72 @@
+// where the runner keeps its files between runs (progress, scores, ...)
+// RUST_BOOK_DATA_DIR wins if it is set, otherwise the platform's usual data directory is used:
+//   $XDG_DATA_HOME/rust_book or ~/.local/share/rust_book on unix, %APPDATA%\rust_book on windows
+
+use std::{
+    env, fs, io,
+    path::PathBuf,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+pub fn data_dir() -> io::Result<PathBuf> {
+    if let Some(dir) = env::var_os("RUST_BOOK_DATA_DIR") {
+        return Ok(PathBuf::from(dir));
+    }
+    let base = if let Some(dir) = env::var_os("XDG_DATA_HOME") {
+        PathBuf::from(dir)
+    } else if let Some(dir) = env::var_os("APPDATA") {
+        PathBuf::from(dir)
+    } else if let Some(home) = env::var_os("HOME") {
+        PathBuf::from(home).join(".local").join("share")
+    } else {
+        return Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            "could not find a data directory, set RUST_BOOK_DATA_DIR",
+        ));
+    };
+    Ok(base.join("rust_book"))
+}
+
+// path of a file in the data directory, creating the directory if needed
+pub fn data_file(name: &str) -> io::Result<PathBuf> {
+    let dir = data_dir()?;
+    fs::create_dir_all(&dir)?;
+    Ok(dir.join(name))
+}
+
+// whoever is logged in, progress and scores are kept per user
+pub fn current_user() -> String {
+    env::var("USER")
+        .or_else(|_| env::var("USERNAME"))
+        .unwrap_or_else(|_| String::from("unknown"))
+}
+
+// seconds since 1970, what gets written to the files
+pub fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+// "2024-03-09 14:05 UTC" from seconds since 1970, without pulling in a date crate
+pub fn format_timestamp(secs: u64) -> String {
+    let days = (secs / 86_400) as i64;
+    let minutes = (secs % 86_400) / 60;
+    // days to a civil date, http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    format!(
+        "{year:04}-{month:02}-{day:02} {:02}:{:02} UTC",
+        minutes / 60,
+        minutes % 60
+    )
+}
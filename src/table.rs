@@ -0,0 +1,228 @@
+// conversion tables, one row per step from start to stop and a column for every unit asked for,
+// printed for the terminal or exported as csv or markdown for handouts
+//
+//   rust_book table F C K --start 0 --stop 100 --step 10 --format markdown
+//   rust_book table ft m cm --start 1 --stop 10 --step 1
+//
+//   |      ℉ |      ℃ |      K |
+//   |-------:|-------:|-------:|
+//   |   0.00 | -17.78 | 255.37 |
+
+use std::{fmt, str::FromStr};
+
+use crate::units::{Quantity, Unit, UnitError};
+
+// a typo in --step shouldn't fill the terminal for a minute
+pub const MAX_ROWS: usize = 10_000;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Style {
+    Plain,
+    Csv,
+    Markdown,
+}
+
+impl FromStr for Style {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "plain" | "text" | "txt" => Ok(Style::Plain),
+            "csv" => Ok(Style::Csv),
+            "markdown" | "md" => Ok(Style::Markdown),
+            _ => Err(format!(
+                "unknown table format \"{s}\", expected plain, csv or markdown"
+            )),
+        }
+    }
+}
+
+impl fmt::Display for Style {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Style::Plain => write!(f, "plain"),
+            Style::Csv => write!(f, "csv"),
+            Style::Markdown => write!(f, "markdown"),
+        }
+    }
+}
+
+// start, start + step, ... up to and including stop (if a step lands on it)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Steps {
+    pub start: f64,
+    pub stop: f64,
+    pub step: f64,
+}
+
+impl Steps {
+    pub fn new(start: f64, stop: f64, step: f64) -> Result<Self, String> {
+        if !(start.is_finite() && stop.is_finite() && step.is_finite()) {
+            return Err(String::from("start, stop and step have to be numbers"));
+        }
+        if step == 0.0 && start != stop {
+            return Err(String::from("a step of 0 never gets anywhere"));
+        }
+        if step != 0.0 && (stop - start) / step < 0.0 {
+            return Err(format!(
+                "a step of {step} goes away from {stop}, try {}",
+                -step
+            ));
+        }
+        let steps = Steps { start, stop, step };
+        if steps.count() > MAX_ROWS {
+            return Err(format!(
+                "that's {} rows, tables stop at {MAX_ROWS}",
+                steps.count()
+            ));
+        }
+        Ok(steps)
+    }
+
+    fn count(&self) -> usize {
+        if self.start == self.stop {
+            return 1;
+        }
+        // a little slack so 0.1 steps still reach their stop
+        ((self.stop - self.start) / self.step + 1e-9).floor() as usize + 1
+    }
+
+    // each value worked out from start rather than by adding up steps, so errors don't build up
+    pub fn values(&self) -> Vec<f64> {
+        (0..self.count())
+            .map(|i| self.start + i as f64 * self.step)
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Table {
+    pub headers: Vec<String>,
+    pub rows: Vec<Vec<f64>>,
+}
+
+impl Table {
+    pub fn render(&self, style: Style, precision: usize) -> String {
+        let rows: Vec<Vec<String>> = self
+            .rows
+            .iter()
+            .map(|row| row.iter().map(|n| number(*n, precision)).collect())
+            .collect();
+        match style {
+            Style::Plain => self.render_plain(&rows),
+            Style::Csv => self.render_csv(&rows),
+            Style::Markdown => self.render_markdown(&rows),
+        }
+    }
+
+    fn widths(&self, rows: &[Vec<String>]) -> Vec<usize> {
+        (0..self.headers.len())
+            .map(|column| {
+                rows.iter()
+                    .map(|row| row[column].chars().count())
+                    .chain([self.headers[column].chars().count()])
+                    .max()
+                    .unwrap_or(0)
+            })
+            .collect()
+    }
+
+    fn render_plain(&self, rows: &[Vec<String>]) -> String {
+        let widths = self.widths(rows);
+        let line = |cells: &[String]| {
+            let cells: Vec<String> = cells
+                .iter()
+                .zip(&widths)
+                .map(|(cell, width)| format!("{cell:>width$}"))
+                .collect();
+            cells.join("  ") + "\n"
+        };
+        let mut text = line(&self.headers);
+        let rule: Vec<String> = widths.iter().map(|width| "-".repeat(*width)).collect();
+        text.push_str(&rule.join("  "));
+        text.push('\n');
+        for row in rows {
+            text.push_str(&line(row));
+        }
+        text
+    }
+
+    fn render_csv(&self, rows: &[Vec<String>]) -> String {
+        let line = |cells: &[String]| {
+            let cells: Vec<String> = cells.iter().map(|cell| csv_field(cell)).collect();
+            cells.join(",") + "\n"
+        };
+        let mut text = line(&self.headers);
+        for row in rows {
+            text.push_str(&line(row));
+        }
+        text
+    }
+
+    fn render_markdown(&self, rows: &[Vec<String>]) -> String {
+        let widths = self.widths(rows);
+        let line = |cells: &[String]| {
+            let cells: Vec<String> = cells
+                .iter()
+                .zip(&widths)
+                .map(|(cell, width)| format!(" {cell:>width$} "))
+                .collect();
+            format!("|{}|\n", cells.join("|"))
+        };
+        let mut text = line(&self.headers);
+        // numbers line up on the right
+        let rule: Vec<String> = widths
+            .iter()
+            .map(|width| format!("{}:", "-".repeat(width + 1)))
+            .collect();
+        text.push_str(&format!("|{}|\n", rule.join("|")));
+        for row in rows {
+            text.push_str(&line(row));
+        }
+        text
+    }
+}
+
+// rounded to `precision` places, without the "-0.00" that rounding a tiny negative gives
+fn number(n: f64, precision: usize) -> String {
+    let text = format!("{n:.precision$}");
+    match text.strip_prefix('-') {
+        Some(rest) if rest.chars().all(|c| c == '0' || c == '.') => rest.to_string(),
+        _ => text,
+    }
+}
+
+// quoted if it has a comma, quote or line break in it
+fn csv_field(cell: &str) -> String {
+    if cell.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", cell.replace('"', "\"\""))
+    } else {
+        cell.to_string()
+    }
+}
+
+// the first column counts up in `from`, the rest are the same quantities in the other units, which
+// all have to measure the same thing. steps below absolute zero are an error rather than a gap
+pub fn conversions(
+    from: &'static Unit,
+    to: &[&'static Unit],
+    steps: &Steps,
+) -> Result<Table, UnitError> {
+    let headers = [from]
+        .iter()
+        .chain(to)
+        .map(|unit| unit.to_string())
+        .collect();
+    let rows = steps
+        .values()
+        .into_iter()
+        .map(|value| {
+            let quantity = Quantity::new(value, from)?;
+            let mut row = vec![value];
+            for unit in to {
+                row.push(quantity.to(unit)?.value);
+            }
+            Ok(row)
+        })
+        .collect::<Result<_, UnitError>>()?;
+    Ok(Table { headers, rows })
+}
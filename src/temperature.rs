@@ -0,0 +1,320 @@
+// temperatures that know their scale, so a ℉ can't be passed where a ℃ is expected
+//
+//   let body: Fahrenheit = "98.6F".parse()?;
+//   println!("{:.1}", Celsius::from(body));        // 37.0℃
+//
+// each scale is described by where absolute zero is on it and how big its degree is, which is all
+// a conversion needs. every temperature at or above absolute zero exists on every scale, so the
+// conversions are plain From (as lossless as f64 allows) and only making a temperature can fail
+
+use std::{fmt, str::FromStr};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum TemperatureError {
+    NotANumber(String),
+    MissingUnit(String),
+    UnknownUnit(String),
+    BelowAbsoluteZero(Temperature),
+}
+
+impl fmt::Display for TemperatureError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TemperatureError::NotANumber(s) => write!(f, "\"{s}\" isn't a number"),
+            TemperatureError::MissingUnit(s) => {
+                write!(f, "\"{s}\" needs a unit, e.g. {s}C or {s}F")
+            }
+            TemperatureError::UnknownUnit(unit) => write!(
+                f,
+                "unknown unit \"{unit}\", expected C, F, K or R (or ℃, ℉)"
+            ),
+            TemperatureError::BelowAbsoluteZero(t) => {
+                write!(f, "{t} is below absolute zero ({})", t.absolute_zero())
+            }
+        }
+    }
+}
+
+impl std::error::Error for TemperatureError {}
+
+// what every scale has to say about itself
+trait ScaleConstants: Copy {
+    // absolute zero in degrees of this scale
+    const ABSOLUTE_ZERO: f64;
+    // kelvins per degree, as a fraction so 5/9 isn't rounded before it has to be
+    const DEGREE: (f64, f64);
+    fn degrees(self) -> f64;
+    fn unchecked(degrees: f64) -> Self;
+}
+
+// a temperature on one scale from one on another, via degrees above absolute zero
+fn convert<From: ScaleConstants, To: ScaleConstants>(t: From) -> To {
+    // the same scale, don't let the arithmetic nudge it
+    if From::ABSOLUTE_ZERO == To::ABSOLUTE_ZERO && From::DEGREE == To::DEGREE {
+        return To::unchecked(t.degrees());
+    }
+    let above_zero = t.degrees() - From::ABSOLUTE_ZERO;
+    let (from_n, from_d) = From::DEGREE;
+    let (to_n, to_d) = To::DEGREE;
+    To::unchecked(above_zero * from_n * to_d / (from_d * to_n) + To::ABSOLUTE_ZERO)
+}
+
+macro_rules! scale {
+    ($name:ident, $symbol:literal, $zero:expr, $degree:expr) => {
+        #[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+        pub struct $name(f64);
+
+        impl $name {
+            pub const ABSOLUTE_ZERO: $name = $name($zero);
+            pub const SYMBOL: &'static str = $symbol;
+
+            pub fn new(degrees: f64) -> Result<Self, TemperatureError> {
+                if !degrees.is_finite() {
+                    return Err(TemperatureError::NotANumber(degrees.to_string()));
+                }
+                if degrees < $zero {
+                    return Err(TemperatureError::BelowAbsoluteZero(Temperature::$name(
+                        $name(degrees),
+                    )));
+                }
+                Ok($name(degrees))
+            }
+
+            pub fn degrees(self) -> f64 {
+                self.0
+            }
+        }
+
+        impl ScaleConstants for $name {
+            const ABSOLUTE_ZERO: f64 = $zero;
+            const DEGREE: (f64, f64) = $degree;
+            fn degrees(self) -> f64 {
+                self.0
+            }
+            fn unchecked(degrees: f64) -> Self {
+                // rounding can land a hair below absolute zero
+                $name(degrees.max($zero))
+            }
+        }
+
+        impl From<Temperature> for $name {
+            fn from(t: Temperature) -> Self {
+                match t {
+                    Temperature::Celsius(c) => convert(c),
+                    Temperature::Fahrenheit(f) => convert(f),
+                    Temperature::Kelvin(k) => convert(k),
+                    Temperature::Rankine(r) => convert(r),
+                }
+            }
+        }
+
+        impl From<$name> for Temperature {
+            fn from(t: $name) -> Self {
+                Temperature::$name(t)
+            }
+        }
+
+        // a plain number is in this scale, anything with a unit is converted to it
+        impl FromStr for $name {
+            type Err = TemperatureError;
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                parse(s, |degrees| $name::new(degrees).map(Temperature::from)).map($name::from)
+            }
+        }
+
+        // {:.2} rounds the number, the symbol goes on after it
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                match f.precision() {
+                    Some(precision) => write!(f, "{:.*}{}", precision, self.0, $symbol),
+                    None => write!(f, "{}{}", self.0, $symbol),
+                }
+            }
+        }
+    };
+}
+
+scale!(Celsius, "℃", -273.15, (1.0, 1.0));
+scale!(Fahrenheit, "℉", -459.67, (5.0, 9.0));
+scale!(Kelvin, "K", 0.0, (1.0, 1.0));
+scale!(Rankine, "°R", 0.0, (5.0, 9.0));
+
+macro_rules! conversions {
+    ($from:ident => $($to:ident),+) => {
+        $(
+            impl From<$from> for $to {
+                fn from(t: $from) -> Self {
+                    convert(t)
+                }
+            }
+        )+
+    };
+}
+
+conversions!(Celsius => Fahrenheit, Kelvin, Rankine);
+conversions!(Fahrenheit => Celsius, Kelvin, Rankine);
+conversions!(Kelvin => Celsius, Fahrenheit, Rankine);
+conversions!(Rankine => Celsius, Fahrenheit, Kelvin);
+
+// a temperature on whichever scale it was written in
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Temperature {
+    Celsius(Celsius),
+    Fahrenheit(Fahrenheit),
+    Kelvin(Kelvin),
+    Rankine(Rankine),
+}
+
+// a scale without a temperature on it, for choosing one (the columns of a table, say)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Scale {
+    Celsius,
+    Fahrenheit,
+    Kelvin,
+    Rankine,
+}
+
+impl Scale {
+    pub const ALL: [Scale; 4] = [
+        Scale::Celsius,
+        Scale::Fahrenheit,
+        Scale::Kelvin,
+        Scale::Rankine,
+    ];
+
+    pub fn symbol(self) -> &'static str {
+        match self {
+            Scale::Celsius => Celsius::SYMBOL,
+            Scale::Fahrenheit => Fahrenheit::SYMBOL,
+            Scale::Kelvin => Kelvin::SYMBOL,
+            Scale::Rankine => Rankine::SYMBOL,
+        }
+    }
+
+    // degrees on this scale in kelvins, without the check for absolute zero
+    pub fn to_kelvins(self, degrees: f64) -> f64 {
+        match self {
+            Scale::Celsius => Kelvin::from(Celsius(degrees)).0,
+            Scale::Fahrenheit => Kelvin::from(Fahrenheit(degrees)).0,
+            Scale::Kelvin => degrees,
+            Scale::Rankine => Kelvin::from(Rankine(degrees)).0,
+        }
+    }
+
+    // a temperature on this scale
+    pub fn at(self, degrees: f64) -> Result<Temperature, TemperatureError> {
+        match self {
+            Scale::Celsius => Celsius::new(degrees).map(Temperature::from),
+            Scale::Fahrenheit => Fahrenheit::new(degrees).map(Temperature::from),
+            Scale::Kelvin => Kelvin::new(degrees).map(Temperature::from),
+            Scale::Rankine => Rankine::new(degrees).map(Temperature::from),
+        }
+    }
+}
+
+impl FromStr for Scale {
+    type Err = TemperatureError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().trim_start_matches('°').to_lowercase().as_str() {
+            "c" | "℃" | "celsius" => Ok(Scale::Celsius),
+            "f" | "℉" | "fahrenheit" => Ok(Scale::Fahrenheit),
+            // U+212A is the kelvin sign, which lowercases to a plain k
+            "k" | "kelvin" | "kelvins" => Ok(Scale::Kelvin),
+            "r" | "ra" | "rankine" => Ok(Scale::Rankine),
+            _ => Err(TemperatureError::UnknownUnit(s.trim().to_string())),
+        }
+    }
+}
+
+impl fmt::Display for Scale {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.symbol())
+    }
+}
+
+impl Temperature {
+    pub fn scale(&self) -> Scale {
+        match self {
+            Temperature::Celsius(_) => Scale::Celsius,
+            Temperature::Fahrenheit(_) => Scale::Fahrenheit,
+            Temperature::Kelvin(_) => Scale::Kelvin,
+            Temperature::Rankine(_) => Scale::Rankine,
+        }
+    }
+
+    pub fn degrees(&self) -> f64 {
+        match self {
+            Temperature::Celsius(t) => t.degrees(),
+            Temperature::Fahrenheit(t) => t.degrees(),
+            Temperature::Kelvin(t) => t.degrees(),
+            Temperature::Rankine(t) => t.degrees(),
+        }
+    }
+
+    // for sums done in kelvins, which are at or above absolute zero when they started out that way
+    pub fn from_kelvins(kelvins: f64) -> Temperature {
+        Temperature::Kelvin(Kelvin(kelvins))
+    }
+
+    // the same temperature on another scale
+    pub fn to(self, scale: Scale) -> Temperature {
+        match scale {
+            Scale::Celsius => Celsius::from(self).into(),
+            Scale::Fahrenheit => Fahrenheit::from(self).into(),
+            Scale::Kelvin => Kelvin::from(self).into(),
+            Scale::Rankine => Rankine::from(self).into(),
+        }
+    }
+
+    pub fn absolute_zero(&self) -> Temperature {
+        match self {
+            Temperature::Celsius(_) => Celsius::ABSOLUTE_ZERO.into(),
+            Temperature::Fahrenheit(_) => Fahrenheit::ABSOLUTE_ZERO.into(),
+            Temperature::Kelvin(_) => Kelvin::ABSOLUTE_ZERO.into(),
+            Temperature::Rankine(_) => Rankine::ABSOLUTE_ZERO.into(),
+        }
+    }
+}
+
+impl fmt::Display for Temperature {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Temperature::Celsius(t) => fmt::Display::fmt(t, f),
+            Temperature::Fahrenheit(t) => fmt::Display::fmt(t, f),
+            Temperature::Kelvin(t) => fmt::Display::fmt(t, f),
+            Temperature::Rankine(t) => fmt::Display::fmt(t, f),
+        }
+    }
+}
+
+// needs a unit, "112F", "-40 °C", "300 kelvin", "491.67°R"
+impl FromStr for Temperature {
+    type Err = TemperatureError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse(s, |_| {
+            Err(TemperatureError::MissingUnit(s.trim().to_string()))
+        })
+    }
+}
+
+// splits "-40 °C" into the number and the unit (everything from the first character that can't be
+// part of a number), `bare` decides what a number on its own means
+fn parse(
+    s: &str,
+    bare: impl Fn(f64) -> Result<Temperature, TemperatureError>,
+) -> Result<Temperature, TemperatureError> {
+    let s = s.trim();
+    let split = s
+        .find(|c: char| !(c.is_ascii_digit() || matches!(c, '-' | '+' | '.' | 'e' | 'E')))
+        .unwrap_or(s.len());
+    let (number, unit) = s.split_at(split);
+    let degrees: f64 = number
+        .trim()
+        .parse()
+        .map_err(|_| TemperatureError::NotANumber(number.trim().to_string()))?;
+    let unit = unit.trim();
+    if unit.is_empty() {
+        return bare(degrees);
+    }
+    unit.parse::<Scale>()?.at(degrees)
+}
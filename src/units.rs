@@ -0,0 +1,416 @@
+// converting between units of length, mass, volume, time and temperature
+//
+//   rust_book convert 5 ft 3 in to cm       5 ft 3 in = 160.02 cm
+//   rust_book convert 3h to s               3 h = 10800 s
+//
+// every unit knows its dimension and how to get to the base unit of that dimension (metres,
+// kilograms, cubic metres, seconds and kelvins). most are a multiple of the base unit, the
+// temperatures are scales from the temperature module, that's how 0℃ ends up as 273.15K
+
+use std::{fmt, io::BufRead};
+
+use crate::output::{say, Report};
+use crate::repl;
+use crate::temperature::{Scale, Temperature, TemperatureError};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Dimension {
+    Length,
+    Mass,
+    Volume,
+    Time,
+    Temperature,
+}
+
+impl Dimension {
+    pub const ALL: [Dimension; 5] = [
+        Dimension::Length,
+        Dimension::Mass,
+        Dimension::Volume,
+        Dimension::Time,
+        Dimension::Temperature,
+    ];
+}
+
+impl fmt::Display for Dimension {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Dimension::Length => "length",
+            Dimension::Mass => "mass",
+            Dimension::Volume => "volume",
+            Dimension::Time => "time",
+            Dimension::Temperature => "temperature",
+        };
+        write!(f, "{name}")
+    }
+}
+
+// how a unit gets to the base unit of its dimension
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Measure {
+    // this many of the base unit
+    Factor(f64),
+    // degrees on a temperature scale, converted to kelvins by the temperature module
+    Scale(Scale),
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Unit {
+    pub name: &'static str,
+    // other ways to write the name, these can be plural too ("3 meters")
+    pub spellings: &'static [&'static str],
+    // the first one is used when printing
+    pub symbols: &'static [&'static str],
+    pub dimension: Dimension,
+    pub measure: Measure,
+}
+
+impl Unit {
+    pub fn symbol(&self) -> &'static str {
+        self.symbols[0]
+    }
+
+    pub fn to_base(&self, value: f64) -> f64 {
+        match self.measure {
+            Measure::Factor(factor) => value * factor,
+            Measure::Scale(scale) => scale.to_kelvins(value),
+        }
+    }
+
+    pub fn from_base(&self, base: f64) -> f64 {
+        match self.measure {
+            Measure::Factor(factor) => base / factor,
+            Measure::Scale(scale) => Temperature::from_kelvins(base).to(scale).degrees(),
+        }
+    }
+}
+
+impl fmt::Display for Unit {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.symbol())
+    }
+}
+
+const fn unit(
+    name: &'static str,
+    symbols: &'static [&'static str],
+    dimension: Dimension,
+    factor: f64,
+) -> Unit {
+    Unit {
+        name,
+        spellings: &[],
+        symbols,
+        dimension,
+        measure: Measure::Factor(factor),
+    }
+}
+
+const fn temperature(name: &'static str, symbols: &'static [&'static str], scale: Scale) -> Unit {
+    Unit {
+        name,
+        spellings: &[],
+        symbols,
+        dimension: Dimension::Temperature,
+        measure: Measure::Scale(scale),
+    }
+}
+
+impl Unit {
+    const fn spelled(self, spellings: &'static [&'static str]) -> Unit {
+        Unit { spellings, ..self }
+    }
+}
+
+// exact definitions where there is one (the inch is 25.4 mm, the pound 0.45359237 kg), us
+// customary volumes
+pub const UNITS: &[Unit] = &[
+    unit("millimetre", &["mm"], Dimension::Length, 0.001).spelled(&["millimeter"]),
+    unit("centimetre", &["cm"], Dimension::Length, 0.01).spelled(&["centimeter"]),
+    unit("metre", &["m"], Dimension::Length, 1.0).spelled(&["meter"]),
+    unit("kilometre", &["km"], Dimension::Length, 1000.0).spelled(&["kilometer"]),
+    unit("inch", &["in", "\""], Dimension::Length, 0.0254).spelled(&["inches"]),
+    unit("foot", &["ft", "'"], Dimension::Length, 0.3048).spelled(&["feet"]),
+    unit("yard", &["yd"], Dimension::Length, 0.9144),
+    unit("mile", &["mi"], Dimension::Length, 1609.344),
+    unit("nautical mile", &["nmi"], Dimension::Length, 1852.0),
+    unit("milligram", &["mg"], Dimension::Mass, 1e-6),
+    unit("gram", &["g"], Dimension::Mass, 0.001),
+    unit("kilogram", &["kg"], Dimension::Mass, 1.0).spelled(&["kilo"]),
+    unit("tonne", &["t"], Dimension::Mass, 1000.0).spelled(&["ton"]),
+    unit("ounce", &["oz"], Dimension::Mass, 0.028349523125),
+    unit("pound", &["lb", "lbs"], Dimension::Mass, 0.45359237),
+    unit("stone", &["st"], Dimension::Mass, 6.35029318),
+    unit("millilitre", &["ml"], Dimension::Volume, 1e-6).spelled(&["milliliter"]),
+    unit("litre", &["l"], Dimension::Volume, 0.001).spelled(&["liter"]),
+    unit("cubic metre", &["m³", "m^3"], Dimension::Volume, 1.0),
+    unit("teaspoon", &["tsp"], Dimension::Volume, 4.92892159375e-6),
+    unit(
+        "tablespoon",
+        &["tbsp"],
+        Dimension::Volume,
+        1.478676478125e-5,
+    ),
+    unit(
+        "fluid ounce",
+        &["floz", "fl-oz"],
+        Dimension::Volume,
+        2.95735295625e-5,
+    ),
+    unit("cup", &["cup"], Dimension::Volume, 2.365882365e-4),
+    unit("pint", &["pt"], Dimension::Volume, 4.73176473e-4),
+    unit("quart", &["qt"], Dimension::Volume, 9.46352946e-4),
+    unit("gallon", &["gal"], Dimension::Volume, 3.785411784e-3),
+    unit("millisecond", &["ms"], Dimension::Time, 0.001),
+    unit("second", &["s", "sec"], Dimension::Time, 1.0),
+    unit("minute", &["min"], Dimension::Time, 60.0),
+    unit("hour", &["h", "hr"], Dimension::Time, 3600.0),
+    unit("day", &["d"], Dimension::Time, 86400.0),
+    unit("week", &["wk"], Dimension::Time, 604800.0),
+    temperature("celsius", &["℃", "c", "°c"], Scale::Celsius),
+    temperature("fahrenheit", &["℉", "f", "°f"], Scale::Fahrenheit),
+    temperature("kelvin", &["K", "k"], Scale::Kelvin),
+    temperature("rankine", &["°R", "r", "ra"], Scale::Rankine),
+];
+
+// by symbol or name, any case, names can be plural ("3 hours") but symbols can't ("cs" isn't
+// a lot of ℃)
+pub fn find(name: &str) -> Option<&'static Unit> {
+    let name = name.to_lowercase();
+    let is = |known: &&str| known.to_lowercase() == name;
+    let plural = |known: &&str| name.strip_suffix('s') == Some(&known.to_lowercase());
+    UNITS
+        .iter()
+        .find(|unit| {
+            [unit.name]
+                .iter()
+                .chain(unit.spellings)
+                .chain(unit.symbols)
+                .any(is)
+        })
+        .or_else(|| {
+            UNITS
+                .iter()
+                .find(|unit| [unit.name].iter().chain(unit.spellings).any(plural))
+        })
+}
+
+pub fn of(dimension: Dimension) -> impl Iterator<Item = &'static Unit> {
+    UNITS.iter().filter(move |unit| unit.dimension == dimension)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum UnitError {
+    UnknownUnit(String),
+    NotANumber(String),
+    Incompatible {
+        from: &'static Unit,
+        to: &'static Unit,
+    },
+    BelowAbsoluteZero(Quantity),
+    // temperatures can be converted but adding them up ("5 C 3 F") means nothing
+    AddedTemperatures,
+    // the expression didn't have the shape "<number> <unit> ... to <unit>"
+    Syntax(String),
+}
+
+impl fmt::Display for UnitError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            UnitError::UnknownUnit(name) => write!(f, "unknown unit \"{name}\""),
+            UnitError::NotANumber(text) => write!(f, "\"{text}\" isn't a number"),
+            UnitError::Incompatible { from, to } => write!(
+                f,
+                "can't convert {from} ({}) to {to} ({})",
+                from.dimension, to.dimension
+            ),
+            UnitError::BelowAbsoluteZero(quantity) => {
+                write!(f, "{quantity} is below absolute zero")
+            }
+            UnitError::AddedTemperatures => write!(f, "temperatures can't be added together"),
+            UnitError::Syntax(message) => write!(f, "{message}"),
+        }
+    }
+}
+
+impl std::error::Error for UnitError {}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Quantity {
+    pub value: f64,
+    pub unit: &'static Unit,
+}
+
+impl Quantity {
+    pub fn new(value: f64, unit: &'static Unit) -> Result<Self, UnitError> {
+        let quantity = Quantity { value, unit };
+        if let Measure::Scale(scale) = unit.measure {
+            if let Err(TemperatureError::BelowAbsoluteZero(_)) = scale.at(value) {
+                return Err(UnitError::BelowAbsoluteZero(quantity));
+            }
+        }
+        Ok(quantity)
+    }
+
+    pub fn to(&self, unit: &'static Unit) -> Result<Quantity, UnitError> {
+        if unit.dimension != self.unit.dimension {
+            return Err(UnitError::Incompatible {
+                from: self.unit,
+                to: unit,
+            });
+        }
+        Ok(Quantity {
+            value: unit.from_base(self.unit.to_base(self.value)),
+            unit,
+        })
+    }
+}
+
+// {:.2} rounds, without a precision there are up to 6 decimals and no trailing zeros
+impl fmt::Display for Quantity {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match f.precision() {
+            Some(precision) => write!(f, "{:.*} {}", precision, self.value, self.unit),
+            None => write!(f, "{} {}", number(self.value), self.unit),
+        }
+    }
+}
+
+// 10800 rather than 10800.000000, and very small or big numbers in scientific notation
+pub fn number(n: f64) -> String {
+    if n != 0.0 && !(1e-4..1e15).contains(&n.abs()) {
+        return format!("{n:.4e}");
+    }
+    let text = format!("{n:.6}");
+    let text = text.trim_end_matches('0').trim_end_matches('.');
+    match text {
+        "-0" => String::from("0"),
+        text => text.to_string(),
+    }
+}
+
+// "5 ft 3 in to cm": the quantities added up, and the unit to give the answer in
+#[derive(Debug, Clone, PartialEq)]
+pub struct Conversion {
+    pub from: Vec<Quantity>,
+    pub to: &'static Unit,
+}
+
+impl Conversion {
+    pub fn parse(text: &str) -> Result<Self, UnitError> {
+        let usage = || {
+            UnitError::Syntax(format!(
+                "expected something like \"5 ft 3 in to cm\", got \"{}\"",
+                text.trim()
+            ))
+        };
+        let tokens = tokens(text);
+        let to = tokens
+            .iter()
+            .rposition(|token| token.eq_ignore_ascii_case("to"));
+        let (quantities, target) = match to {
+            Some(i) if i + 2 == tokens.len() => (&tokens[..i], &tokens[i + 1]),
+            _ => return Err(usage()),
+        };
+        if quantities.is_empty() || quantities.len() % 2 != 0 {
+            return Err(usage());
+        }
+        let from = quantities
+            .chunks(2)
+            .map(|pair| {
+                // f64 also reads "nan", "inf" and 1e999, none of which convert to anything
+                let value = pair[0]
+                    .parse()
+                    .ok()
+                    .filter(|value: &f64| value.is_finite())
+                    .ok_or_else(|| UnitError::NotANumber(pair[0].clone()))?;
+                let unit = find(&pair[1]).ok_or_else(|| UnitError::UnknownUnit(pair[1].clone()))?;
+                Quantity::new(value, unit)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let to = find(target).ok_or_else(|| UnitError::UnknownUnit(target.clone()))?;
+        Ok(Conversion { from, to })
+    }
+
+    // the quantities added up in the target unit
+    pub fn result(&self) -> Result<Quantity, UnitError> {
+        if self.from.len() > 1 && self.to.dimension == Dimension::Temperature {
+            return Err(UnitError::AddedTemperatures);
+        }
+        let mut base = 0.0;
+        for quantity in &self.from {
+            if quantity.unit.dimension != self.to.dimension {
+                return Err(UnitError::Incompatible {
+                    from: quantity.unit,
+                    to: self.to,
+                });
+            }
+            base += quantity.unit.to_base(quantity.value);
+        }
+        Ok(Quantity {
+            value: self.to.from_base(base),
+            unit: self.to,
+        })
+    }
+}
+
+impl fmt::Display for Conversion {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let from: Vec<String> = self.from.iter().map(Quantity::to_string).collect();
+        write!(f, "{}", from.join(" "))
+    }
+}
+
+// converts each expression typed in until exit, for the repl
+pub fn converter(input: &mut dyn BufRead, out: &mut dyn Report) {
+    say!(
+        out,
+        "Type a conversion like \"5 ft 3 in to cm\" or \"3h to s\", \"exit\" to stop."
+    );
+    while let Some(line) = repl::prompt_line(input, out, "convert> ") {
+        match Conversion::parse(&line).and_then(|conversion| Ok((conversion.result()?, conversion)))
+        {
+            Ok((result, conversion)) => say!(out, "{conversion} = {result}"),
+            Err(e) => say!(out, "{e}"),
+        }
+    }
+}
+
+// splits on spaces and where a number meets a unit, "5ft3in" is 5 ft 3 in
+fn tokens(text: &str) -> Vec<String> {
+    let mut tokens: Vec<String> = Vec::new();
+    // whether the token being read is a number, None between tokens
+    let mut number = None;
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        let next_is_digit = chars
+            .peek()
+            .is_some_and(|n| n.is_ascii_digit() || *n == '.');
+        let same_token = match number {
+            _ if c.is_whitespace() => {
+                number = None;
+                continue;
+            }
+            None => false,
+            Some(true) => {
+                let last = tokens.last().and_then(|token| token.chars().last());
+                c.is_ascii_digit()
+                    || c == '.'
+                    // an exponent, 1e3 or 1e-3
+                    || (matches!(c, 'e' | 'E') && (next_is_digit || chars.peek() == Some(&'-')))
+                    || (matches!(c, '-' | '+') && matches!(last, Some('e' | 'E')))
+            }
+            // m^3 is one unit
+            Some(false) => !c.is_ascii_digit() || tokens.last().is_some_and(|t| t.ends_with('^')),
+        };
+        if same_token {
+            tokens.last_mut().unwrap().push(c);
+        } else {
+            let starts_number =
+                c.is_ascii_digit() || c == '.' || (matches!(c, '-' | '+') && next_is_digit);
+            number = Some(starts_number);
+            tokens.push(c.to_string());
+        }
+    }
+    tokens
+}
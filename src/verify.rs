@@ -0,0 +1,153 @@
+// checks the snippets in snippets.rs against the real compiler: each one is written to a temp dir,
+// compiled with the local rustc ($RUSTC if it is set) and, if it builds, run to see if it panics
+//
+//   ok    4 double-mutable-borrow         does not compile (error[E0499])
+//   FAIL  3 assign-immutable              expected compiles but panics at runtime, got does not compile (error[E0384])
+
+use std::{
+    env, fs, io,
+    path::{Path, PathBuf},
+    process::{self, Command},
+    thread,
+};
+
+use crate::output::{say, Report};
+use crate::snippets::{Outcome, Snippet};
+
+// what actually happened, like snippets::Outcome but the error code comes from rustc's output
+#[derive(Debug, Clone, PartialEq)]
+pub enum Found {
+    CompileError(Option<String>),
+    Panic,
+    Compiles,
+}
+
+impl Found {
+    pub fn matches(&self, expected: Outcome) -> bool {
+        match (self, expected) {
+            (Found::CompileError(found), Outcome::CompileError(code)) => found.as_deref() == code,
+            (Found::Panic, Outcome::Panic) | (Found::Compiles, Outcome::Compiles) => true,
+            _ => false,
+        }
+    }
+
+    pub fn describe(&self) -> String {
+        match self {
+            Found::CompileError(Some(code)) => format!("does not compile (error[{code}])"),
+            Found::CompileError(None) => String::from("does not compile"),
+            Found::Panic => String::from("compiles but panics at runtime"),
+            Found::Compiles => String::from("compiles and runs fine"),
+        }
+    }
+}
+
+pub struct Check {
+    pub snippet: &'static Snippet,
+    // an error when rustc couldn't be started or the program died some other way
+    pub found: io::Result<Found>,
+}
+
+impl Check {
+    pub fn passed(&self) -> bool {
+        matches!(&self.found, Ok(found) if found.matches(self.snippet.outcome))
+    }
+}
+
+pub fn rustc() -> PathBuf {
+    env::var_os("RUSTC")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from("rustc"))
+}
+
+// the first error code in rustc's output, e.g. "E0499" from "error[E0499]: cannot borrow ..."
+pub fn error_code(stderr: &str) -> Option<String> {
+    stderr.lines().find_map(|line| {
+        let code = line.strip_prefix("error[")?.split(']').next()?;
+        Some(code.to_string())
+    })
+}
+
+// compiles (and runs) one snippet inside dir
+pub fn check(snippet: &Snippet, dir: &Path) -> io::Result<Found> {
+    let name = format!("chapter_{}_{}", snippet.chapter, snippet.name).replace('-', "_");
+    let source = dir.join(format!("{name}.rs"));
+    let binary = dir.join(format!("{name}{}", env::consts::EXE_SUFFIX));
+    fs::write(&source, snippet.code)?;
+
+    let compiled = Command::new(rustc())
+        .args(["--edition", "2021", "-A", "warnings", "-o"])
+        .arg(&binary)
+        .arg(&source)
+        .output()?;
+    if !compiled.status.success() {
+        let stderr = String::from_utf8_lossy(&compiled.stderr);
+        return Ok(Found::CompileError(error_code(&stderr)));
+    }
+
+    let ran = Command::new(&binary).output()?;
+    match ran.status.code() {
+        Some(0) => Ok(Found::Compiles),
+        // the exit code of a panic, see chapter nine
+        Some(101) => Ok(Found::Panic),
+        _ => Err(io::Error::other(format!(
+            "{name} exited with {}",
+            ran.status
+        ))),
+    }
+}
+
+// checks every snippet, a few at a time since most of the time is spent waiting for rustc
+pub fn check_all(snippets: &[&'static Snippet]) -> io::Result<Vec<Check>> {
+    let dir = env::temp_dir().join(format!("rust_book_verify_{}", process::id()));
+    fs::create_dir_all(&dir)?;
+    let workers = thread::available_parallelism().map_or(1, |n| n.get());
+    let mut checks = Vec::new();
+    for batch in snippets.chunks(workers) {
+        thread::scope(|scope| {
+            let handles: Vec<_> = batch
+                .iter()
+                .map(|snippet| scope.spawn(|| check(snippet, &dir)))
+                .collect();
+            for (snippet, handle) in batch.iter().zip(handles) {
+                let found = handle
+                    .join()
+                    .unwrap_or_else(|_| Err(io::Error::other("the check panicked")));
+                checks.push(Check { snippet, found });
+            }
+        });
+    }
+    let _ = fs::remove_dir_all(&dir);
+    Ok(checks)
+}
+
+// one line per snippet then a total, returns whether they all did what the catalog says
+pub fn report(checks: &[Check], out: &mut dyn Report) -> bool {
+    for check in checks {
+        let snippet = check.snippet;
+        let status = if check.passed() { "ok" } else { "FAIL" };
+        let detail = match &check.found {
+            Ok(found) if check.passed() => found.describe(),
+            Ok(found) => format!(
+                "expected {}, got {}",
+                snippet.outcome.describe(),
+                found.describe()
+            ),
+            Err(e) => format!("could not check: {e}"),
+        };
+        say!(
+            out,
+            "{status:<5} {:>2} {:<30} {detail}",
+            snippet.chapter,
+            snippet.name
+        );
+    }
+    let failed = checks.iter().filter(|check| !check.passed()).count();
+    say!(out);
+    say!(
+        out,
+        "{} of {} snippets do what the notes say",
+        checks.len() - failed,
+        checks.len()
+    );
+    failed == 0
+}
@@ -0,0 +1,103 @@
+use rust_book::bigint::BigUint;
+
+fn big(s: &str) -> BigUint {
+    s.parse().unwrap()
+}
+
+#[test]
+fn parsing_and_printing_round_trip() {
+    for text in [
+        "0",
+        "7",
+        "999999999",
+        "1000000000",
+        "123456789012345678901234567890",
+    ] {
+        assert_eq!(big(text).to_string(), text);
+    }
+    assert_eq!(big("000120").to_string(), "120");
+    assert_eq!(big("1_000_000").to_string(), "1000000");
+    assert_eq!(format!("{:>6}|{:<4}|", big("42"), big("7")), "    42|7   |");
+    for bad in ["", "-1", "1.5", "_1", "12a"] {
+        assert!(bad.parse::<BigUint>().is_err(), "{bad}");
+    }
+}
+
+#[test]
+fn addition_carries_across_limbs() {
+    assert_eq!(
+        big("999999999999999999") + big("1"),
+        big("1000000000000000000")
+    );
+    assert_eq!(
+        &big("1") + &big("999999999999999999"),
+        big("1000000000000000000")
+    );
+    assert_eq!(big("0") + big("0"), BigUint::zero());
+    let max = BigUint::from(u128::MAX);
+    assert_eq!(u128::try_from(&max), Ok(u128::MAX));
+    assert_eq!(u128::try_from(&(&max + &BigUint::one())), Err(()));
+    assert_eq!(
+        (&max + &max).to_string(),
+        "680564733841876926926749214863536422910"
+    );
+}
+
+#[test]
+fn ordering_goes_by_value() {
+    let mut numbers = vec![big("1000000000"), big("0"), big("999999999"), big("10")];
+    numbers.sort();
+    assert_eq!(
+        numbers,
+        [big("0"), big("10"), big("999999999"), big("1000000000")]
+    );
+    assert_eq!(big("1000000000").digits(), 10);
+    assert_eq!(BigUint::zero().digits(), 1);
+}
+
+#[test]
+fn multiplying_subtracting_and_remainders() {
+    let a = big("123456789012345678901234567890");
+    let b = big("987654321098765432109876543210");
+    assert_eq!(
+        (&a * &b).to_string(),
+        "121932631137021795226185032733622923332237463801111263526900"
+    );
+    assert_eq!(&a * &BigUint::zero(), BigUint::zero());
+    assert_eq!((&b - &a).to_string(), "864197532086419753208641975320");
+    assert_eq!(&a - &a, BigUint::zero());
+    assert_eq!(a.checked_sub(&b), None);
+    assert_eq!(
+        big("1000000000").checked_sub(&big("1")),
+        Some(big("999999999"))
+    );
+    assert_eq!(
+        a.rem_u64(97),
+        (123456789012345678901234567890u128 % 97) as u64
+    );
+    assert_eq!(
+        b.rem_u64(u64::MAX),
+        (987654321098765432109876543210u128 % u64::MAX as u128) as u64
+    );
+}
+
+#[test]
+fn bits_come_out_highest_first() {
+    let bits = |n: &BigUint| -> String {
+        n.bits()
+            .iter()
+            .map(|bit| if *bit { '1' } else { '0' })
+            .collect()
+    };
+    assert_eq!(bits(&BigUint::zero()), "");
+    assert_eq!(bits(&big("6")), "110");
+    let n = 987654321098765432109876543210u128;
+    assert_eq!(bits(&big(&n.to_string())), format!("{n:b}"));
+    assert_eq!(bits(&big(&u128::MAX.to_string())), "1".repeat(128));
+}
+
+#[test]
+#[should_panic(expected = "attempt to subtract with overflow")]
+fn subtracting_a_bigger_number_panics() {
+    let _ = &big("1") - &big("2");
+}
@@ -0,0 +1,161 @@
+use std::io::Cursor;
+
+use rust_book::expr::{self, Expr, ExprError, Ty, Type, Value};
+use rust_book::literal::LiteralError;
+use rust_book::output::{Capture, Event};
+
+fn eval(text: &str) -> Value {
+    expr::evaluate(text).unwrap_or_else(|e| panic!("{text}: {e}"))
+}
+
+fn error(text: &str) -> String {
+    expr::evaluate(text).unwrap_err().to_string()
+}
+
+#[test]
+fn the_operators_from_chapter_three() {
+    assert_eq!(eval("5 + 10"), Value::I32(15));
+    assert_eq!(eval("95.5 - 4.3"), Value::F64(95.5 - 4.3));
+    assert_eq!(eval("4 * 30"), Value::I32(120));
+    assert_eq!(eval("56.7 / 32.2"), Value::F64(56.7 / 32.2));
+    assert_eq!(eval("-5 / 10"), Value::I32(0));
+    assert_eq!(eval("43 % 10"), Value::I32(3));
+}
+
+#[test]
+fn division_truncates_and_remainders_follow_the_left_side() {
+    assert_eq!(eval("-7 / 2"), Value::I32(-3));
+    assert_eq!(eval("-7 % 3"), Value::I32(-1));
+    assert_eq!(eval("7 % -3"), Value::I32(1));
+    assert_eq!(eval("-7.5 % 2.0"), Value::F64(-1.5));
+    assert_eq!(eval("(1 + 2) * 3 - 4 / 2"), Value::I32(7));
+    assert_eq!(eval("2 - 3 - 4"), Value::I32(-5));
+}
+
+#[test]
+fn literals_take_their_type_from_the_other_side() {
+    assert_eq!(eval("56.7f32 / 32.2"), Value::F32(56.7f32 / 32.2));
+    assert_eq!(eval("200 + 55u8"), Value::U8(255));
+    assert_eq!(eval("3_000_000_000i64 * 2"), Value::I64(6_000_000_000));
+    assert_eq!(eval("2f32"), Value::F32(2.0));
+    assert_eq!(eval("1e3"), Value::F64(1000.0));
+    assert_eq!("1 + 2".parse::<Expr>().unwrap().ty(), Ok(Ty::Integer));
+    assert_eq!("1.5 * 2.0".parse::<Expr>().unwrap().ty(), Ok(Ty::Float));
+    assert_eq!(
+        "1 + 2usize".parse::<Expr>().unwrap().ty(),
+        Ok(Ty::Known(Type::Usize))
+    );
+}
+
+#[test]
+fn mixing_types_is_an_error() {
+    assert_eq!(error("1u8 + 1i32"), "cannot add `i32` to `u8`");
+    assert_eq!(error("1 + 2.0"), "cannot add `{float}` to `{integer}`");
+    assert_eq!(error("2.0f32 * 2"), "cannot multiply `f32` by `{integer}`");
+    assert_eq!(error("1i64 - 1.0"), "cannot subtract `{float}` from `i64`");
+    assert_eq!(
+        error("-1u32"),
+        "cannot apply unary operator `-` to type `u32`"
+    );
+    assert_eq!(
+        error("-1 * 2u16"),
+        "cannot apply unary operator `-` to type `u16`"
+    );
+    // casting is how it's done
+    assert_eq!(eval("1u8 as i32 + 1i32"), Value::I32(2));
+}
+
+#[test]
+fn literals_have_to_fit_their_type() {
+    assert_eq!(
+        error("256u8"),
+        "literal out of range for `u8`: the literal `256u8` does not fit into the type `u8` whose range is `0..=255`"
+    );
+    assert_eq!(
+        error("3_000_000_000"),
+        "literal out of range for `i32`: the literal `3_000_000_000` does not fit into the type `i32` whose range is `-2147483648..=2147483647`"
+    );
+    assert_eq!(eval("-128i8"), Value::I8(-128));
+    assert!(error("128i8").starts_with("literal out of range for `i8`: the literal `128i8`"));
+    assert_eq!(
+        eval("-170141183460469231731687303715884105728i128"),
+        Value::I128(i128::MIN)
+    );
+    assert!(matches!(
+        expr::evaluate("99999999999999999999999999999999999999999"),
+        Err(ExprError::Literal(LiteralError::TooLarge))
+    ));
+    // the other bases and bytes work in expressions too
+    assert_eq!(eval("0xff_u8 - b'A'"), Value::U8(190));
+    assert_eq!(eval("0b1111_0000 + 0o77"), Value::I32(303));
+}
+
+#[test]
+fn overflow_and_dividing_by_zero_panic_but_floats_dont() {
+    assert_eq!(error("255u8 + 1"), "attempt to add with overflow");
+    assert_eq!(error("-1i8 as u8 * 2"), "attempt to multiply with overflow");
+    assert_eq!(error("-(-128i8)"), "attempt to negate with overflow");
+    assert_eq!(error("1 / 0"), "attempt to divide by zero");
+    assert_eq!(error("-2147483648 / -1"), "attempt to divide with overflow");
+    assert_eq!(
+        error("1 % 0"),
+        "attempt to calculate the remainder with a divisor of zero"
+    );
+    assert_eq!(eval("1.0 / 0.0"), Value::F64(f64::INFINITY));
+    assert!(matches!(eval("0.0 / 0.0"), Value::F64(n) if n.is_nan()));
+}
+
+#[test]
+fn an_integer_as_takes_the_literal_with_it() {
+    // the literal is a u8, not an i32 that's cast
+    assert!(error("300 as u8").starts_with("literal out of range for `u8`"));
+    assert_eq!(
+        error("-1 as u32"),
+        "cannot apply unary operator `-` to type `u32`"
+    );
+    assert_eq!(eval("255 as u8"), Value::U8(255));
+    assert_eq!(eval("-128 as i8"), Value::I8(-128));
+    assert_eq!(expr::evaluate("(2 + 3) as u8").unwrap(), Value::U8(5));
+    // a float target doesn't, that's an i32 cast
+    assert_eq!(eval("16777217 as f32"), Value::F32(16777216.0));
+}
+
+#[test]
+fn casts_wrap_integers_and_saturate_floats() {
+    assert_eq!(eval("200u8 as i8"), Value::I8(-56));
+    assert_eq!(eval("-1i32 as u8"), Value::U8(255));
+    assert_eq!(eval("300.7 as u8"), Value::U8(255));
+    assert_eq!(eval("-1.5 as u32"), Value::U32(0));
+    assert_eq!(eval("(0.0 / 0.0) as i32"), Value::I32(0));
+    assert_eq!(
+        eval("340282366920938463463374607431768211455u128 as f32"),
+        Value::F32(u128::MAX as f32)
+    );
+    assert_eq!(eval("16777217 as f32"), Value::F32(16777216.0));
+    assert_eq!(eval("0.1f32 as f64"), Value::F64(0.1f32 as f64));
+}
+
+#[test]
+fn the_calculator_shows_the_type() {
+    let mut capture = Capture::new();
+    expr::calculator(
+        &mut Cursor::new("-5 / 10\n1 +\n56.7f32 / 32.2\nexit\n"),
+        &mut capture,
+    );
+    let lines: Vec<&str> = capture
+        .events
+        .iter()
+        .filter_map(|event| match event {
+            Event::Line(line) if line != "eval> " => Some(line.as_str()),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(
+        lines[1..],
+        [
+            "0: i32",
+            "expected a number, found the end",
+            "1.7608695: f32"
+        ]
+    );
+}
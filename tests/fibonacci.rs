@@ -0,0 +1,197 @@
+use rust_book::bigint::BigUint;
+use rust_book::fib_n;
+use rust_book::fibonacci::{self, FibError, Fibonacci, Recurrence};
+
+#[test]
+fn checked_versions_agree_with_fib_n() {
+    let homework = fib_n(47);
+    assert_eq!(fibonacci::try_fib_n(47).unwrap(), homework);
+    let wide = fibonacci::fib_n_u64(47).unwrap();
+    assert!(wide.iter().zip(&homework).all(|(a, b)| *a == *b as u64));
+    // fib_n's quirks at the small end aren't copied
+    assert_eq!(fibonacci::try_fib_n(0).unwrap(), []);
+    assert_eq!(fibonacci::try_fib_n(1).unwrap(), [0]);
+    assert_eq!(fibonacci::try_fib_n(2).unwrap(), [0, 1]);
+}
+
+#[test]
+fn each_type_says_where_it_runs_out() {
+    assert_eq!(
+        fibonacci::try_fib_n(48),
+        Err(FibError::Overflow {
+            asked: 48,
+            max: 47,
+            type_name: "i32"
+        })
+    );
+    assert_eq!(fibonacci::try_fib_n(-1), Err(FibError::Negative(-1)));
+    assert_eq!(
+        fibonacci::fib_n_u64(94).unwrap().last(),
+        Some(&12_200_160_415_121_876_738)
+    );
+    assert!(matches!(
+        fibonacci::fib_n_u64(95),
+        Err(FibError::Overflow { max: 94, .. })
+    ));
+    assert_eq!(
+        fibonacci::fib_n_u128(187).unwrap().last(),
+        Some(&332_825_110_087_067_562_321_196_029_789_634_457_848)
+    );
+    assert_eq!(
+        fibonacci::fib_n_u128(188).unwrap_err().to_string(),
+        "188 fibonacci numbers don't fit in u128, it holds the first 187"
+    );
+}
+
+#[test]
+fn big_numbers_match_u128_while_it_lasts() {
+    let big = fibonacci::fib_n_big(187);
+    let small = fibonacci::fib_n_u128(187).unwrap();
+    for (big, small) in big.iter().zip(small) {
+        assert_eq!(*big, BigUint::from(small));
+        assert_eq!(u128::try_from(big), Ok(small));
+    }
+}
+
+#[test]
+fn ten_thousand_numbers_are_exact() {
+    let fibs = fibonacci::fib_n_big(10_000);
+    assert_eq!(fibs.len(), 10_000);
+    let last = fibs.last().unwrap().to_string();
+    assert_eq!(last.len(), 2090);
+    assert!(last.starts_with("207936082371334980721126489886"));
+    assert!(last.ends_with("908796650294603536651238230626"));
+    let digits: usize = fibs.iter().map(BigUint::digits).sum();
+    assert_eq!(digits, 10_449_845);
+}
+
+#[test]
+fn the_iterator_is_lazy_and_stops_before_overflowing() {
+    let small: Vec<u64> = Fibonacci::new().take_while(|n| *n < 100).collect();
+    assert_eq!(small, [0, 1, 1, 2, 3, 5, 8, 13, 21, 34, 55, 89]);
+    assert_eq!(Fibonacci::<i32>::new().count(), 47);
+    assert_eq!(Fibonacci::<u128>::new().count(), 187);
+    // big numbers go on as long as they're asked for
+    assert_eq!(
+        Fibonacci::<BigUint>::new().nth(300).unwrap().to_string(),
+        "222232244629420445529739893461909967206666939096499764990979600"
+    );
+}
+
+#[test]
+fn fast_doubling_matches_counting_up() {
+    let fibs = fibonacci::fib_n_big(300);
+    for (n, fib) in fibs.iter().enumerate() {
+        assert_eq!(fibonacci::nth_fib(n as u64), *fib, "F({n})");
+    }
+    let big = fibonacci::nth_fib(100_000).to_string();
+    assert_eq!(big.len(), 20_899);
+    assert!(big.starts_with("25974069347221724166"));
+    assert!(big.ends_with("49895374653428746875"));
+}
+
+#[test]
+fn remainders_go_round_the_pisano_period() {
+    let periods: Vec<u64> = (1..=10)
+        .map(|m| fibonacci::pisano_period(m).unwrap())
+        .collect();
+    assert_eq!(periods, [1, 3, 8, 6, 20, 24, 16, 12, 24, 60]);
+    assert_eq!(fibonacci::pisano_period(0), Err(FibError::ZeroModulus));
+
+    let fibs = fibonacci::fib_n_big(500);
+    // 65_536 is the biggest modulus that goes round the period, 65_537 only doubles
+    for m in [1, 2, 7, 10, 1000, 65_536, 65_537] {
+        for (n, fib) in fibs.iter().enumerate() {
+            assert_eq!(
+                fibonacci::fib_mod(&BigUint::from(n as u64), m),
+                Ok(fib.rem_u64(m)),
+                "F({n}) mod {m}"
+            );
+        }
+    }
+    let googol: BigUint = format!("1{}", "0".repeat(100)).parse().unwrap();
+    // the last three digits of F(10^100), which has far more digits than there are atoms
+    assert_eq!(fibonacci::fib_mod(&googol, 1000), Ok(875));
+    // F(n) and F(n + period) have the same remainder
+    let period = fibonacci::pisano_period(65_536).unwrap();
+    let shifted = BigUint::from(123 + period);
+    assert_eq!(
+        fibonacci::fib_mod(&shifted, 65_536),
+        Ok(fibs[123].rem_u64(65_536))
+    );
+}
+
+#[test]
+fn big_moduli_dont_need_the_period() {
+    let hundred = BigUint::from(100u64);
+    assert_eq!(
+        fibonacci::fib_mod(&hundred, 1_000_000_007),
+        Ok(fibonacci::nth_fib(100).rem_u64(1_000_000_007))
+    );
+    assert_eq!(
+        fibonacci::fib_mod(&hundred, 1_000_000_000_000),
+        Ok(179_261_915_075)
+    );
+    let googol: BigUint = format!("1{}", "0".repeat(100)).parse().unwrap();
+    assert_eq!(
+        fibonacci::fib_mod(&googol, 1_000_000_000_000),
+        Ok(299_560_546_875)
+    );
+    assert_eq!(fibonacci::fib_mod(&googol, u64::MAX).map(|_| ()), Ok(()));
+    assert_eq!(fibonacci::fib_mod(&hundred, 0), Err(FibError::ZeroModulus));
+}
+
+#[test]
+fn recurrences_cover_the_named_sequences() {
+    let first = |recurrence: &Recurrence| recurrence.terms().take(10).collect::<Vec<_>>();
+    assert_eq!(
+        first(&Recurrence::fibonacci()),
+        [0, 1, 1, 2, 3, 5, 8, 13, 21, 34]
+    );
+    assert_eq!(
+        first(&Recurrence::lucas()),
+        [2, 1, 3, 4, 7, 11, 18, 29, 47, 76]
+    );
+    assert_eq!(
+        first(&Recurrence::tribonacci()),
+        [0, 0, 1, 1, 2, 4, 7, 13, 24, 44]
+    );
+    assert_eq!(
+        first(&Recurrence::pell()),
+        [0, 1, 2, 5, 12, 29, 70, 169, 408, 985]
+    );
+    // the fibonacci rule starting somewhere else is the lucas numbers
+    assert_eq!(
+        Recurrence::fibonacci().starting_with(vec![2, 1]),
+        Ok(Recurrence::lucas())
+    );
+    assert_eq!(
+        Recurrence::named("Lucas").unwrap().to_string(),
+        "a(n) = a(n-1) + a(n-2), starting 2, 1"
+    );
+}
+
+#[test]
+fn recurrences_can_be_made_up() {
+    // a(n) = 3a(n-1) - 2a(n-2) from 0, 1 is 2^n - 1
+    let recurrence = Recurrence::new(vec![3, -2], vec![0, 1]).unwrap();
+    assert_eq!(
+        recurrence.to_string(),
+        "a(n) = 3a(n-1) - 2a(n-2), starting 0, 1"
+    );
+    let terms: Vec<i128> = recurrence.terms().collect();
+    // 3 * (2^126 - 1) doesn't fit, even though 2^127 - 1 would
+    assert_eq!(terms.len(), 127);
+    assert!(terms
+        .iter()
+        .enumerate()
+        .all(|(n, a)| *a == (1i128 << n) - 1));
+    assert_eq!(
+        Recurrence::new(vec![1, 1], vec![1]),
+        Err(FibError::Mismatch {
+            coefficients: 2,
+            initial: 1
+        })
+    );
+    assert!(Recurrence::new(vec![], vec![]).is_err());
+}
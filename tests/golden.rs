@@ -0,0 +1,98 @@
+// compares every chapter's output against tests/snapshots, see src/golden.rs
+use std::{fs, path::Path};
+
+// shared with the binary, which is the only one to bless
+#[allow(dead_code)]
+#[path = "../src/golden.rs"]
+mod golden;
+
+use golden::{render, snapshot_path, CASES};
+
+fn dir() -> &'static Path {
+    Path::new(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/snapshots"))
+}
+
+fn check(name: &str) {
+    let case = CASES
+        .iter()
+        .find(|case| case.name == name)
+        .expect("no golden case with that name");
+    let path = snapshot_path(dir(), case);
+    let expected = fs::read_to_string(&path).unwrap_or_else(|e| {
+        panic!(
+            "could not read {}: {e}\nrun `cargo run -- bless` to create it",
+            path.display()
+        )
+    });
+    let actual = render(case);
+    if actual != expected {
+        let line = actual
+            .lines()
+            .zip(expected.lines())
+            .position(|(a, e)| a != e)
+            .unwrap_or_else(|| actual.lines().count().min(expected.lines().count()));
+        panic!(
+            "{name} no longer matches {} (first difference on line {})\n\
+             --- expected ---\n{expected}\n--- actual ---\n{actual}\n\
+             run `cargo run -- bless` if the change is intended",
+            path.display(),
+            line + 1
+        );
+    }
+}
+
+#[test]
+fn chapter_one() {
+    check("chapter_one");
+}
+
+#[test]
+fn chapter_three() {
+    check("chapter_three");
+}
+
+#[test]
+fn chapter_three_hw() {
+    check("chapter_three_hw");
+}
+
+#[test]
+fn chapter_four() {
+    check("chapter_four");
+}
+
+#[test]
+fn chapter_five() {
+    check("chapter_five");
+}
+
+#[test]
+fn chapter_six() {
+    check("chapter_six");
+}
+
+#[test]
+fn chapter_eight() {
+    check("chapter_eight");
+}
+
+#[test]
+fn chapter_eight_hw_median_and_mode() {
+    check("chapter_eight_hw_median_and_mode");
+}
+
+#[test]
+fn chapter_eight_hw_pig_latin() {
+    check("chapter_eight_hw_pig_latin");
+}
+
+#[test]
+fn every_case_has_a_snapshot() {
+    for case in CASES {
+        assert!(
+            snapshot_path(dir(), case).exists(),
+            "missing snapshot for {}, run `cargo run -- bless`",
+            case.name
+        );
+    }
+}
@@ -0,0 +1,195 @@
+// the guessing game played from a script, a range with a single number makes the secret known
+use std::io::Cursor;
+
+use rand::{rngs::StdRng, SeedableRng};
+
+use rust_book::guessing_game::{
+    self, Difficulty, End, Feedback, GuessingGame, Hint, NoHint, Outcome, Settings,
+};
+use rust_book::output::{Capture, Event};
+use rust_book::Guess;
+
+fn play(settings: &Settings, script: &str) -> (guessing_game::Summary, Vec<String>) {
+    let mut capture = Capture::new();
+    let summary = guessing_game::play(settings, &mut Cursor::new(script), &mut capture);
+    let lines = capture
+        .events
+        .into_iter()
+        .filter_map(|event| match event {
+            Event::Line(line) if line != guessing_game::PROMPT => Some(line),
+            _ => None,
+        })
+        .collect();
+    (summary, lines)
+}
+
+#[test]
+fn bad_input_gets_feedback_and_costs_nothing() {
+    let settings = Settings::new(7..=7, Some(1)).unwrap();
+    let (summary, lines) = play(&settings, "seven\n0\n7\n");
+    assert_eq!(summary.end, End::Won);
+    assert_eq!(summary.guesses, [7]);
+    assert!(lines.contains(&String::from("\"seven\" is not a number, try again.")));
+    assert!(lines.contains(&String::from(
+        "guess must be between 7 and 7, got 0, try again."
+    )));
+    assert!(lines.contains(&String::from("You win! You got 7 in 1 guess.")));
+}
+
+#[test]
+fn leaving_gives_up() {
+    let settings = Settings::new(5..=5, None).unwrap();
+    let (summary, lines) = play(&settings, "exit\n");
+    assert_eq!(summary.end, End::GaveUp);
+    assert!(summary.guesses.is_empty());
+    assert_eq!(lines.last().unwrap(), "The number was 5.");
+}
+
+#[test]
+fn seeded_games_repeat() {
+    let secret =
+        |seed| GuessingGame::new(Settings::default(), &mut StdRng::seed_from_u64(seed)).secret();
+    assert_eq!(secret(11), secret(11));
+    assert!((1..=100).contains(&secret(11)));
+}
+
+#[test]
+fn guesses_move_the_game_along() {
+    let mut game = GuessingGame::with_secret(Settings::new(1..=100, Some(3)).unwrap(), 42);
+    assert_eq!(game.guess(50), Outcome::TooBig);
+    assert!(matches!(game.guess(101), Outcome::OutOfRange(_)));
+    assert_eq!(game.guess(25), Outcome::TooSmall);
+    assert_eq!(game.attempts_left(), Some(1));
+    assert_eq!(game.end(), None);
+    assert_eq!(game.guess(42), Outcome::Correct);
+    assert_eq!(game.end(), Some(End::Won));
+    assert_eq!(game.guess(42), Outcome::GameOver);
+    assert_eq!(game.guesses(), [50, 25, 42]);
+}
+
+#[test]
+fn running_out_of_attempts_loses() {
+    let mut game = GuessingGame::with_secret(Settings::new(1..=100, Some(2)).unwrap(), 42);
+    game.guess(1);
+    game.guess(2);
+    assert_eq!(game.end(), Some(End::OutOfAttempts));
+    game.give_up();
+    assert_eq!(game.summary().end, End::OutOfAttempts);
+}
+
+#[test]
+fn scripted_session_with_a_seeded_game() {
+    let settings = Settings::new(1..=1000, Some(10)).unwrap();
+    let mut game = GuessingGame::new(settings.clone(), &mut StdRng::seed_from_u64(3));
+    let secret = game.secret();
+    let script = format!("500\n{}\n{secret}\n", secret + 1);
+    let mut capture = Capture::new();
+    let summary = guessing_game::drive(&mut game, &mut Cursor::new(script), &mut capture);
+    assert_eq!(summary.end, End::Won);
+    assert_eq!(summary.guesses.len(), 3);
+    assert!(capture.events.contains(&Event::Line(format!(
+        "You win! You got {secret} in 3 guesses."
+    ))));
+}
+
+#[test]
+fn hot_cold_compares_with_the_previous_guess() {
+    let settings = Settings::new(1..=100, None)
+        .unwrap()
+        .with_feedback(Feedback::HotCold);
+    let mut game = GuessingGame::with_secret(settings, 40);
+    assert_eq!(game.guess(90), Outcome::Cold);
+    assert_eq!(game.guess(60), Outcome::Warmer);
+    assert_eq!(game.guess(20), Outcome::SameDistance);
+    assert_eq!(game.guess(10), Outcome::Colder);
+    assert_eq!(game.guess(40), Outcome::Correct);
+
+    let settings = Settings::new(1..=100, None)
+        .unwrap()
+        .with_feedback(Feedback::HotCold);
+    assert_eq!(
+        GuessingGame::with_secret(settings.clone(), 40).guess(45),
+        Outcome::Hot
+    );
+    assert_eq!(
+        GuessingGame::with_secret(settings, 40).guess(55),
+        Outcome::Warm
+    );
+}
+
+#[test]
+fn hints_cost_an_attempt() {
+    let mut game = GuessingGame::with_secret(Settings::new(1..=100, Some(3)).unwrap(), 30);
+    assert_eq!(game.hint(), Ok(Hint::Even(true)));
+    assert_eq!(game.hint(), Ok(Hint::DivisibleBy(3, true)));
+    assert_eq!(game.attempts_left(), Some(1));
+    // the last attempt is kept for guessing
+    assert_eq!(game.hint(), Err(NoHint::NotEnoughAttempts));
+    assert_eq!(game.guess(30), Outcome::Correct);
+    assert_eq!(game.summary().attempts(), 3);
+    assert_eq!(game.hint(), Err(NoHint::GameOver));
+
+    let mut game = GuessingGame::with_secret(Settings::new(1..=100, None).unwrap(), 7);
+    let hints: Vec<String> = (0..6).map(|_| game.hint().unwrap().to_string()).collect();
+    assert_eq!(hints[0], "the number is odd");
+    assert_eq!(hints[4], "the number is divisible by 7");
+    assert_eq!(game.hint(), Err(NoHint::NoneLeft));
+}
+
+#[test]
+fn the_liar_lies_as_often_as_configured() {
+    let liar = |p| {
+        Settings::new(1..=100, None)
+            .unwrap()
+            .with_feedback(Feedback::Liar(p))
+    };
+    assert_eq!(
+        GuessingGame::with_secret(liar(1.0), 50).guess(10),
+        Outcome::TooBig
+    );
+    assert_eq!(
+        GuessingGame::with_secret(liar(0.0), 50).guess(10),
+        Outcome::TooSmall
+    );
+
+    // the lies come from the rng too, so seeded games repeat
+    let answers = |seed| {
+        let mut game = GuessingGame::new(liar(0.5), &mut StdRng::seed_from_u64(seed));
+        (1..=20).map(|n| game.guess(n)).collect::<Vec<_>>()
+    };
+    assert_eq!(answers(5), answers(5));
+}
+
+#[test]
+fn hints_in_a_scripted_session() {
+    let mut game = GuessingGame::with_secret(Settings::new(1..=10, Some(5)).unwrap(), 4);
+    let mut capture = Capture::new();
+    let summary = guessing_game::drive(&mut game, &mut Cursor::new("hint\n4\n"), &mut capture);
+    assert_eq!(summary.hints, 1);
+    let lines = capture.events;
+    assert!(lines.contains(&Event::Line(String::from("Hint: the number is even."))));
+    assert!(lines.contains(&Event::Line(String::from(
+        "You win! You got 4 in 1 guess and 1 hint."
+    ))));
+}
+
+#[test]
+fn ranges_parse_like_rust() {
+    assert_eq!(guessing_game::parse_range("1..=500"), Ok(1..=500));
+    assert_eq!(guessing_game::parse_range("-10..10"), Ok(-10..=9));
+    assert!(guessing_game::parse_range("1-500").is_err());
+    let backwards = guessing_game::parse_range("10..=1").unwrap();
+    assert!(Settings::new(backwards, None).is_err());
+    assert!(Settings::new(1..=10, Some(0)).is_err());
+    assert_eq!("HARD".parse::<Difficulty>(), Ok(Difficulty::Hard));
+    assert_eq!("liar:0.1".parse::<Feedback>(), Ok(Feedback::Liar(0.1)));
+    assert_eq!("hot-cold".parse::<Feedback>(), Ok(Feedback::HotCold));
+    assert!("liar:2".parse::<Feedback>().is_err());
+}
+
+#[test]
+fn guesses_can_be_checked_without_panicking() {
+    assert_eq!(Guess::between(1000, 1, 1000).unwrap().value(), 1000);
+    let e = Guess::between(101, 1, 100).err().unwrap();
+    assert_eq!(e.to_string(), "guess must be between 1 and 100, got 101");
+}
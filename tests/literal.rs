@@ -0,0 +1,151 @@
+use rust_book::expr::{Ty, Type, Value};
+use rust_book::literal::{Base, Kind, Literal, LiteralError};
+
+fn literal(text: &str) -> Literal {
+    text.parse().unwrap_or_else(|e| panic!("{text}: {e}"))
+}
+
+fn value(text: &str) -> Value {
+    literal(text)
+        .value()
+        .unwrap_or_else(|e| panic!("{text}: {e}"))
+}
+
+fn error(text: &str) -> String {
+    match text.parse::<Literal>() {
+        Ok(literal) => literal.value().unwrap_err().to_string(),
+        Err(e) => e.to_string(),
+    }
+}
+
+#[test]
+fn every_form_from_the_notes() {
+    assert_eq!(value("98_222"), Value::I32(98_222));
+    assert_eq!(value("0xff"), Value::I32(255));
+    assert_eq!(value("0o77"), Value::I32(63));
+    assert_eq!(value("0b1111_0000"), Value::I32(240));
+    assert_eq!(value("b'A'"), Value::U8(65));
+    assert_eq!(value("57u8"), Value::U8(57));
+    assert_eq!(
+        literal("0b1111_0000").kind,
+        Kind::Integer(240, Base::Binary)
+    );
+}
+
+#[test]
+fn suffixes_and_the_defaults() {
+    assert_eq!(literal("1").ty(), Ty::Integer);
+    assert_eq!(literal("1.5").ty(), Ty::Float);
+    assert_eq!(literal("1_000_i64").ty(), Ty::Known(Type::I64));
+    assert_eq!(literal("b'a'").ty(), Ty::Known(Type::U8));
+    assert_eq!(value("0xffu8"), Value::U8(255));
+    // a decimal with a float suffix is a float, and so is 1.
+    assert_eq!(value("2f32"), Value::F32(2.0));
+    assert_eq!(value("1."), Value::F64(1.0));
+    assert_eq!(value("2.5"), Value::F64(2.5));
+    assert_eq!(
+        literal("0xff").value_as(Type::U8, false),
+        Ok(Value::U8(255))
+    );
+}
+
+#[test]
+fn exponents_make_floats() {
+    assert_eq!(value("1e6"), Value::F64(1e6));
+    assert_eq!(value("6.02e23_f64"), Value::F64(6.02e23));
+    assert_eq!(value("2.5E-3f32"), Value::F32(2.5e-3));
+    assert_eq!(value("1_0e1_0"), Value::F64(1e11));
+    assert_eq!(error("1e"), "expected at least one digit in exponent");
+    assert_eq!(error("1em"), "expected at least one digit in exponent");
+    assert_eq!(error("1.5e"), "expected at least one digit in exponent");
+    assert_eq!(error("1e+"), "expected at least one digit in exponent");
+    assert_eq!(error("1.5e_"), "expected at least one digit in exponent");
+}
+
+#[test]
+fn bytes_and_their_escapes() {
+    assert_eq!(value("b'\\n'"), Value::U8(b'\n'));
+    assert_eq!(value("b'\\x7f'"), Value::U8(0x7f));
+    assert_eq!(value("b'\\''"), Value::U8(b'\''));
+    assert_eq!(value("b'\\0'"), Value::U8(0));
+    assert_eq!(error("b''"), "empty byte literal");
+    assert_eq!(error("b'é'"), "non-ASCII character in byte literal");
+    assert_eq!(error("b'ab'"), "byte literal may only contain one byte");
+    assert_eq!(error("b'a"), "unterminated byte constant");
+    assert_eq!(error("b'a'u8"), "suffixes on byte literals are invalid");
+    assert_eq!(error("b'\\q'"), "unknown byte escape: `q`");
+}
+
+#[test]
+fn mistakes_rustc_wont_lex() {
+    assert_eq!(error("0o78"), "invalid digit `8` for a base 8 literal");
+    assert_eq!(error("0b102"), "invalid digit `2` for a base 2 literal");
+    assert_eq!(error("0x"), "no valid digits found for number");
+    assert_eq!(error("0xg"), "no valid digits found for number");
+    assert_eq!(error("0b_u8"), "no valid digits found for number");
+    assert_eq!(error("12kg"), "invalid suffix `kg` for number literal");
+    assert_eq!(error("1.5u8"), "invalid suffix `u8` for float literal");
+    assert_eq!(error("0x1.8"), "hexadecimal float literal is not supported");
+    assert_eq!(error("0b1f32"), "binary float literal is not supported");
+    assert_eq!(
+        error("340282366920938463463374607431768211456"),
+        "integer literal is too large"
+    );
+    assert_eq!(
+        "1 + 1".parse::<Literal>(),
+        Err(LiteralError::NotALiteral(String::from("1 + 1")))
+    );
+}
+
+#[test]
+fn out_of_range_like_rustc() {
+    assert_eq!(
+        error("256u8"),
+        "literal out of range for `u8`: the literal `256u8` does not fit into the type `u8` whose range is `0..=255`"
+    );
+    assert_eq!(
+        error("0xffi8"),
+        "literal out of range for `i8`: the literal `0xffi8` (decimal `255`) does not fit into the type `i8` and will become `-1i8`"
+    );
+    assert_eq!(
+        error("1e40f32"),
+        "literal out of range for `f32`: the literal `1e40f32` does not fit into the type `f32` and will be converted to `f32::INFINITY`"
+    );
+    // the minus makes -128 fit where 128 doesn't
+    assert_eq!(
+        literal("128i8").value_as(Type::I8, true),
+        Ok(Value::I8(-128))
+    );
+    assert!(matches!(
+        literal("129i8").value_as(Type::I8, true),
+        Err(LiteralError::OutOfRange { .. })
+    ));
+    assert_eq!(
+        literal("1u8").value_as(Type::U8, true),
+        Err(LiteralError::Negate(Type::U8))
+    );
+}
+
+#[test]
+fn a_leading_minus_negates_the_literal() {
+    assert_eq!(value("-128i8"), Value::I8(-128));
+    assert_eq!(value("-2.5f32"), Value::F32(-2.5));
+    assert_eq!(value("-0x80i8"), Value::I8(-128));
+    assert_eq!(literal("-128i8").to_string(), "-128i8");
+    assert_eq!(
+        error("-129i8"),
+        "literal out of range for `i8`: the literal `-129i8` does not fit into the type `i8` whose range is `-128..=127`"
+    );
+    assert_eq!(
+        error("-1u8"),
+        "cannot apply unary operator `-` to type `u8`"
+    );
+    assert_eq!(
+        literal("-b'A'").value(),
+        Err(LiteralError::Negate(Type::U8))
+    );
+    assert!(matches!(
+        "--1".parse::<Literal>(),
+        Err(LiteralError::NotALiteral(_))
+    ));
+}
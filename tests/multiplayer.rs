@@ -0,0 +1,272 @@
+// games over real connections on 127.0.0.1, port 0 so the tests don't fight over a port
+use std::{
+    io::{self, Write},
+    net::TcpStream,
+    thread::{self, JoinHandle},
+    time::Duration,
+};
+
+use rand::{rngs::StdRng, SeedableRng};
+
+use rust_book::multiplayer::{Client, Message, Mode, Request, Server, ServerSettings, Verdict};
+
+// a server for two players in the background, and its secret
+fn serve(mode: Mode) -> (Server, i32) {
+    let settings = ServerSettings {
+        range: 1..=100,
+        mode,
+        players: 2,
+    };
+    let server = Server::bind("127.0.0.1:0", settings, &mut StdRng::seed_from_u64(15)).unwrap();
+    let secret = server.secret();
+    (server, secret)
+}
+
+fn start(
+    server: Server,
+    names: [&str; 2],
+) -> (JoinHandle<io::Result<Option<String>>>, Vec<Client>) {
+    let addr = server.local_addr().unwrap();
+    let handle = thread::spawn(move || server.run());
+    let clients = names
+        .into_iter()
+        .map(|name| {
+            let client = Client::connect(addr, name).unwrap();
+            // a broken server fails the test instead of hanging it
+            client
+                .stream()
+                .set_read_timeout(Some(Duration::from_secs(5)))
+                .unwrap();
+            client
+        })
+        .collect();
+    (handle, clients)
+}
+
+// reads messages until one matches, the joins can arrive in either order
+fn until(client: &mut Client, wanted: impl Fn(&Message) -> bool) -> Message {
+    loop {
+        let message = client.receive().unwrap().expect("the server hung up");
+        if wanted(&message) {
+            return message;
+        }
+    }
+}
+
+fn started(clients: &mut [Client]) {
+    for client in clients {
+        until(client, |message| *message == Message::Start);
+    }
+}
+
+#[test]
+fn the_first_correct_guess_wins_a_race() {
+    let (server, secret) = serve(Mode::Race);
+    let (handle, mut clients) = start(server, ["alice", "bob"]);
+    started(&mut clients);
+
+    let wrong = if secret == 100 { 1 } else { 100 };
+    clients[1].guess(wrong).unwrap();
+    for client in clients.iter_mut() {
+        let result = until(client, |message| matches!(message, Message::Result { .. }));
+        assert_eq!(
+            result,
+            Message::Result {
+                player: String::from("bob"),
+                guess: wrong,
+                verdict: if wrong < secret {
+                    Verdict::TooSmall
+                } else {
+                    Verdict::TooBig
+                },
+            }
+        );
+    }
+
+    clients[0].guess(secret).unwrap();
+    let winner = Message::Winner {
+        player: String::from("alice"),
+        guess: secret,
+        attempts: 1,
+    };
+    for client in clients.iter_mut() {
+        assert_eq!(
+            until(client, |message| matches!(message, Message::Winner { .. })),
+            winner
+        );
+    }
+    assert_eq!(handle.join().unwrap().unwrap(), Some(String::from("alice")));
+}
+
+#[test]
+fn players_take_turns_in_the_order_they_joined() {
+    let (server, secret) = serve(Mode::Turns);
+    let (handle, mut clients) = start(server, ["alice", "bob"]);
+    started(&mut clients);
+    assert_eq!(
+        clients[1].receive().unwrap(),
+        Some(Message::Turn(String::from("alice")))
+    );
+
+    // out of turn
+    clients[1].guess(secret).unwrap();
+    assert_eq!(
+        clients[1].receive().unwrap(),
+        Some(Message::Error(String::from("it's alice's turn")))
+    );
+
+    let wrong = if secret == 1 { 2 } else { 1 };
+    clients[0].guess(wrong).unwrap();
+    until(&mut clients[1], |message| {
+        *message == Message::Turn(String::from("bob"))
+    });
+    clients[1].guess(secret).unwrap();
+    assert_eq!(
+        until(&mut clients[0], |message| matches!(
+            message,
+            Message::Winner { .. }
+        )),
+        Message::Winner {
+            player: String::from("bob"),
+            guess: secret,
+            attempts: 1,
+        }
+    );
+    assert_eq!(handle.join().unwrap().unwrap(), Some(String::from("bob")));
+}
+
+#[test]
+fn a_player_leaving_passes_their_turn_on() {
+    let (server, secret) = serve(Mode::Turns);
+    let (handle, mut clients) = start(server, ["alice", "bob"]);
+    started(&mut clients);
+
+    // it's alice's turn when she goes
+    let alice = clients.remove(0);
+    drop(alice);
+    let bob = &mut clients[0];
+    until(bob, |message| {
+        *message == Message::Left(String::from("alice"))
+    });
+    assert_eq!(
+        bob.receive().unwrap(),
+        Some(Message::Turn(String::from("bob")))
+    );
+    // out of range guesses are only told to the player, and aren't their turn over
+    bob.guess(0).unwrap();
+    assert_eq!(
+        until(bob, |message| matches!(message, Message::Error(_))),
+        Message::Error(String::from("guess must be between 1 and 100, got 0"))
+    );
+    bob.guess(secret).unwrap();
+    until(bob, |message| matches!(message, Message::Winner { .. }));
+    assert_eq!(handle.join().unwrap().unwrap(), Some(String::from("bob")));
+
+    // the server closes every connection once the game is over
+    assert_eq!(clients[0].receive().unwrap(), None);
+}
+
+#[test]
+fn a_player_leaving_before_naming_isnt_waited_for() {
+    let settings = ServerSettings {
+        range: 1..=100,
+        mode: Mode::Turns,
+        players: 3,
+    };
+    let server = Server::bind("127.0.0.1:0", settings, &mut StdRng::seed_from_u64(15)).unwrap();
+    let secret = server.secret();
+    let addr = server.local_addr().unwrap();
+    // the first to connect hangs up without a name, so the first turn isn't theirs
+    drop(TcpStream::connect(addr).unwrap());
+    let handle = thread::spawn(move || server.run());
+    let mut clients: Vec<Client> = ["alice", "bob"]
+        .into_iter()
+        .map(|name| {
+            let client = Client::connect(addr, name).unwrap();
+            client
+                .stream()
+                .set_read_timeout(Some(Duration::from_secs(5)))
+                .unwrap();
+            client
+        })
+        .collect();
+    started(&mut clients);
+    assert_eq!(
+        clients[1].receive().unwrap(),
+        Some(Message::Turn(String::from("alice")))
+    );
+    clients[0].guess(secret).unwrap();
+    assert_eq!(handle.join().unwrap().unwrap(), Some(String::from("alice")));
+}
+
+#[test]
+fn the_server_explains_bad_requests() {
+    let (server, _) = serve(Mode::Race);
+    let addr = server.local_addr().unwrap();
+    let handle = thread::spawn(move || server.run());
+    let mut alice = Client::connect(addr, "alice").unwrap();
+    alice
+        .stream()
+        .set_read_timeout(Some(Duration::from_secs(5)))
+        .unwrap();
+    assert!(matches!(
+        alice.receive().unwrap(),
+        Some(Message::Welcome {
+            mode: Mode::Race,
+            players: 2,
+            ..
+        })
+    ));
+    alice.guess(50).unwrap();
+    writeln!(alice.stream(), "GUESS fifty").unwrap();
+    let mut bob = Client::connect(addr, "alice").unwrap();
+    assert_eq!(
+        until(&mut alice, |message| matches!(message, Message::Error(_))),
+        Message::Error(String::from("the game starts when everyone has joined"))
+    );
+    assert_eq!(
+        until(&mut alice, |message| matches!(message, Message::Error(_))),
+        Message::Error(String::from("\"fifty\" is not a number"))
+    );
+    bob.stream()
+        .set_read_timeout(Some(Duration::from_secs(5)))
+        .unwrap();
+    assert_eq!(
+        until(&mut bob, |message| matches!(message, Message::Error(_))),
+        Message::Error(String::from("alice is taken"))
+    );
+    drop((alice, bob));
+    assert_eq!(handle.join().unwrap().unwrap(), None);
+}
+
+#[test]
+fn messages_survive_the_trip_through_a_line() {
+    let messages = [
+        Message::Welcome {
+            range: -5..=5,
+            mode: Mode::Turns,
+            players: 3,
+        },
+        Message::Joined(String::from("ferris")),
+        Message::Start,
+        Message::Turn(String::from("ferris")),
+        Message::Result {
+            player: String::from("ferris"),
+            guess: -3,
+            verdict: Verdict::TooBig,
+        },
+        Message::Winner {
+            player: String::from("ferris"),
+            guess: 4,
+            attempts: 2,
+        },
+        Message::Left(String::from("ferris")),
+        Message::Error(String::from("it's ferris's turn")),
+    ];
+    for message in messages {
+        assert_eq!(message.to_string().parse::<Message>(), Ok(message));
+    }
+    assert_eq!("GUESS 7".parse::<Request>(), Ok(Request::Guess(7)));
+    assert!("NAME two words".parse::<Request>().is_err());
+    assert!("HELLO".parse::<Message>().is_err());
+}
@@ -0,0 +1,91 @@
+use rust_book::numbers::{self, cardinal_words, ordinal, ordinal_words};
+
+#[test]
+fn ordinals_get_the_right_ending() {
+    let endings: Vec<String> = [1, 2, 3, 4, 11, 12, 13, 21, 22, 23, 101, 111, 112, 1013]
+        .into_iter()
+        .map(ordinal::<u16>)
+        .collect();
+    assert_eq!(
+        endings,
+        [
+            "1st", "2nd", "3rd", "4th", "11th", "12th", "13th", "21st", "22nd", "23rd", "101st",
+            "111th", "112th", "1013th"
+        ]
+    );
+    assert_eq!(ordinal(0u8), "0th");
+    assert_eq!(ordinal(-2i8), "-2nd");
+    assert_eq!(ordinal(u128::MAX), format!("{}th", u128::MAX));
+}
+
+#[test]
+fn numbers_are_spelled_out() {
+    assert_eq!(cardinal_words(0u8), "zero");
+    assert_eq!(cardinal_words(13i16), "thirteen");
+    assert_eq!(cardinal_words(40u32), "forty");
+    assert_eq!(cardinal_words(-42i64), "minus forty-two");
+    assert_eq!(cardinal_words(100usize), "one hundred");
+    assert_eq!(cardinal_words(101u8), "one hundred and one");
+    assert_eq!(cardinal_words(1005u16), "one thousand and five");
+    assert_eq!(
+        cardinal_words(1_234_567u32),
+        "one million two hundred and thirty-four thousand five hundred and sixty-seven"
+    );
+    assert_eq!(cardinal_words(2_000_000_000u32), "two billion");
+    assert_eq!(
+        cardinal_words(i8::MIN),
+        "minus one hundred and twenty-eight"
+    );
+}
+
+#[test]
+fn every_width_goes_all_the_way_to_its_limits() {
+    assert_eq!(
+        cardinal_words(u64::MAX),
+        "eighteen quintillion four hundred and forty-six quadrillion seven hundred and forty-four \
+         trillion seventy-three billion seven hundred and nine million five hundred and fifty-one \
+         thousand six hundred and fifteen"
+    );
+    assert!(cardinal_words(u128::MAX)
+        .starts_with("three hundred and forty undecillion two hundred and eighty-two decillion"));
+    // i128::MIN has no positive i128 to match, its size still fits in a u128
+    assert!(cardinal_words(i128::MIN).starts_with("minus one hundred and seventy undecillion"));
+    assert!(cardinal_words(i128::MIN).ends_with("seven hundred and twenty-eight"));
+    assert_eq!(
+        numbers::cardinal_words(isize::MAX),
+        numbers::cardinal_words(isize::MAX as u64)
+    );
+}
+
+#[test]
+fn ordinals_are_spelled_out() {
+    let words: Vec<String> = [
+        0, 1, 2, 3, 4, 5, 8, 9, 12, 20, 21, 40, 99, 100, 101, 1000, 1_000_002,
+    ]
+    .into_iter()
+    .map(ordinal_words::<i32>)
+    .collect();
+    assert_eq!(
+        words,
+        [
+            "zeroth",
+            "first",
+            "second",
+            "third",
+            "fourth",
+            "fifth",
+            "eighth",
+            "ninth",
+            "twelfth",
+            "twentieth",
+            "twenty-first",
+            "fortieth",
+            "ninety-ninth",
+            "one hundredth",
+            "one hundred and first",
+            "one thousandth",
+            "one million and second"
+        ]
+    );
+    assert_eq!(ordinal_words(-3i8), "minus third");
+}
@@ -0,0 +1,145 @@
+use std::io::Cursor;
+
+use rust_book::output::{Capture, Event};
+use rust_book::overflow::{self, Expression, Op, Outcome, Width};
+
+fn evaluate(width: Width, expression: &str) -> overflow::Results {
+    overflow::evaluate(width, &expression.parse().unwrap()).unwrap()
+}
+
+fn value(text: &str) -> Outcome {
+    Outcome::Value(text.to_string())
+}
+
+#[test]
+fn every_mode_of_an_overflowing_add() {
+    let results = evaluate(Width::U8, "250 + 10");
+    assert_eq!(results.wrapping, value("4"));
+    assert_eq!(results.checked, Outcome::Nothing);
+    assert_eq!(
+        results.overflowing,
+        Outcome::Flagged(String::from("4"), true)
+    );
+    assert_eq!(results.saturating, value("255"));
+    assert_eq!(
+        results.debug,
+        Outcome::Panic("attempt to add with overflow")
+    );
+    assert_eq!(results.release, value("4"));
+
+    let fine = evaluate(Width::I16, "250 + 10");
+    assert_eq!(fine.checked, value("Some(260)"));
+    assert_eq!(fine.debug, value("260"));
+}
+
+#[test]
+fn max_and_min_belong_to_each_type() {
+    for (width, wrapped) in [
+        (Width::I8, "-128"),
+        (Width::U16, "0"),
+        (Width::I128, "-170141183460469231731687303715884105728"),
+    ] {
+        assert_eq!(
+            evaluate(width, "MAX + 1").wrapping,
+            value(wrapped),
+            "{width}"
+        );
+    }
+    assert_eq!(
+        evaluate(Width::I32, "min - 1").saturating,
+        value("-2147483648")
+    );
+    assert_eq!(
+        evaluate(Width::U64, "MAX * 2").saturating,
+        value("18446744073709551615")
+    );
+    assert_eq!(
+        evaluate(Width::I8, "neg MIN").debug,
+        Outcome::Panic("attempt to negate with overflow")
+    );
+    // -x doesn't compile for unsigned types
+    assert_eq!(evaluate(Width::U8, "neg 1").debug, Outcome::Missing);
+    assert_eq!(evaluate(Width::U8, "neg 1").wrapping, value("255"));
+}
+
+#[test]
+fn division_panics_even_in_release_builds() {
+    let by_zero = evaluate(Width::U32, "7 / 0");
+    assert_eq!(by_zero.checked, Outcome::Nothing);
+    for outcome in [
+        by_zero.wrapping,
+        by_zero.overflowing,
+        by_zero.debug,
+        by_zero.release,
+    ] {
+        assert_eq!(outcome, Outcome::Panic("attempt to divide by zero"));
+    }
+    let min = evaluate(Width::I8, "MIN / -1");
+    assert_eq!(min.wrapping, value("-128"));
+    assert_eq!(min.saturating, value("127"));
+    assert_eq!(
+        min.release,
+        Outcome::Panic("attempt to divide with overflow")
+    );
+    // no saturating_rem
+    assert_eq!(evaluate(Width::I8, "MIN % -1").saturating, Outcome::Missing);
+    assert_eq!(evaluate(Width::I8, "-7 % 2").wrapping, value("-1"));
+}
+
+#[test]
+fn shifts_wrap_the_amount_not_the_bits() {
+    let results = evaluate(Width::U8, "1 << 9");
+    assert_eq!(results.wrapping, value("2"));
+    assert_eq!(results.checked, Outcome::Nothing);
+    assert_eq!(
+        results.debug,
+        Outcome::Panic("attempt to shift left with overflow")
+    );
+    // bits shifted off the end aren't an overflow, only shifting by the width or more
+    assert_eq!(evaluate(Width::U8, "255 << 4").debug, value("240"));
+    assert_eq!(
+        evaluate(Width::I32, "2 pow 31").debug,
+        Outcome::Panic("attempt to multiply with overflow")
+    );
+    assert_eq!(evaluate(Width::I64, "2 pow 31").debug, value("2147483648"));
+}
+
+#[test]
+fn operands_have_to_fit() {
+    let expression: Expression = "300 + 1".parse().unwrap();
+    assert_eq!(
+        overflow::evaluate(Width::I8, &expression),
+        Err(String::from("300 is out of range for i8"))
+    );
+    assert!(overflow::evaluate(Width::I16, &expression).is_ok());
+    assert!(overflow::evaluate(Width::U8, &"1 << 99999999999".parse().unwrap()).is_err());
+    assert!("x + 1".parse::<Expression>().is_err());
+    assert!("1 ^ 2".parse::<Expression>().is_err());
+    assert!("1 neg 2".parse::<Expression>().is_err());
+    assert_eq!("2 POW 8".parse::<Expression>().unwrap().op, Op::Pow);
+}
+
+#[test]
+fn the_explorer_shows_every_width() {
+    let mut capture = Capture::new();
+    overflow::explorer(&mut Cursor::new("250 + 10\nexit\n"), &mut capture);
+    let rows: Vec<&str> = capture
+        .events
+        .iter()
+        .filter_map(|event| match event {
+            Event::Line(line) => Some(line.as_str()),
+            _ => None,
+        })
+        .filter(|line| {
+            Width::ALL
+                .iter()
+                .any(|width| line.starts_with(&format!("{width} ")))
+        })
+        .collect();
+    assert_eq!(rows.len(), 10);
+    assert_eq!(rows[0], "i8    (250 is out of range for i8)");
+    assert!(rows[5].starts_with("u8    4 "));
+    assert!(capture.events.contains(&Event::Line(String::from(
+        "panic: \"attempt to add with overflow\""
+    ))));
+}
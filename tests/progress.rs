@@ -0,0 +1,78 @@
+// the progress file round trip and the "next up" suggestion
+use std::fs;
+
+use rust_book::progress::{self, Entry};
+
+mod common;
+use common::TempDir;
+
+fn entry(user: &str, chapter: &str, section: Option<&str>, passed: bool) -> Entry {
+    Entry {
+        user: user.to_string(),
+        chapter: chapter.to_string(),
+        section: section.map(str::to_string),
+        timestamp: 1_700_000_000,
+        passed,
+    }
+}
+
+#[test]
+fn entries_survive_a_round_trip() {
+    let dir = TempDir::new("progress");
+    let path = dir.file("round_trip.tsv");
+    let entries = vec![
+        entry("ferris", "4", Some("slices"), true),
+        entry("ferris", "3-hw", None, false),
+    ];
+    progress::record(&path, &entries[..1]).unwrap();
+    progress::record(&path, &entries[1..]).unwrap();
+    assert_eq!(progress::load(&path).unwrap(), entries);
+}
+
+#[test]
+fn tabs_and_newlines_in_a_name_dont_split_the_line() {
+    let dir = TempDir::new("progress");
+    let path = dir.file("names.tsv");
+    progress::record(&path, &[entry("fer\tris\n", "4", None, true)]).unwrap();
+    assert_eq!(
+        progress::load(&path).unwrap(),
+        [entry("fer ris ", "4", None, true)]
+    );
+}
+
+#[test]
+fn missing_and_damaged_files_are_not_errors() {
+    let dir = TempDir::new("progress");
+    let path = dir.file("damaged.tsv");
+    assert!(progress::load(&path).unwrap().is_empty());
+
+    fs::write(
+        &path,
+        "ferris\t1\t-\t1700000000\tpassed\nnot a line\nferris\t2\t-\tsoon\tpassed\n",
+    )
+    .unwrap();
+    assert_eq!(
+        progress::load(&path).unwrap(),
+        [entry("ferris", "1", None, true)]
+    );
+}
+
+#[test]
+fn next_up_is_the_first_section_not_passed() {
+    let entries = vec![
+        entry("ferris", "1", None, true),
+        entry("ferris", "2", None, true),
+        entry("ferris", "3", Some("variables"), true),
+        entry("ferris", "3", Some("constants"), false),
+        // someone else's progress doesn't count
+        entry("corro", "3", None, true),
+    ];
+    assert_eq!(
+        progress::next_up(&entries, "ferris"),
+        Some((String::from("3"), Some("constants")))
+    );
+    assert_eq!(
+        progress::next_up(&entries, "nobody"),
+        Some((String::from("1"), None))
+    );
+}
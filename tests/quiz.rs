@@ -0,0 +1,90 @@
+// scripted quiz sessions, the reasons are shuffled with a seeded rng so the runs repeat
+use std::io::Cursor;
+
+use rand::{rngs::StdRng, SeedableRng};
+
+use rust_book::chapter;
+use rust_book::output::Capture;
+use rust_book::quiz::{self, Score};
+use rust_book::snippets::{self, Outcome, SNIPPETS};
+
+fn letter(outcome: Outcome) -> &'static str {
+    match outcome {
+        Outcome::CompileError(_) => "c",
+        Outcome::Panic => "p",
+        Outcome::Compiles => "s",
+    }
+}
+
+fn play(questions: &[&snippets::Snippet], script: &str) -> Score {
+    let mut rng = StdRng::seed_from_u64(8);
+    quiz::run(
+        questions,
+        &mut rng,
+        &mut Cursor::new(script),
+        &mut Capture::new(),
+    )
+}
+
+#[test]
+fn outcomes_are_scored() {
+    let questions = snippets::from_chapter(None);
+    let right: String = questions
+        .iter()
+        .map(|snippet| format!("{}\n1\n", letter(snippet.outcome)))
+        .collect();
+    let score = play(&questions, &right);
+    assert_eq!(score.asked, SNIPPETS.len());
+    assert_eq!(score.outcomes, SNIPPETS.len());
+
+    let wrong: String = questions
+        .iter()
+        .map(|snippet| match snippet.outcome {
+            Outcome::Compiles => "p\n1\n",
+            _ => "s\n1\n",
+        })
+        .collect();
+    assert_eq!(play(&questions, &wrong).outcomes, 0);
+}
+
+#[test]
+fn exactly_one_reason_is_right() {
+    let questions = snippets::from_chapter(Some("4"));
+    let snippet = &questions[..1];
+    let right: Vec<usize> = (1..=4)
+        .filter(|n| play(snippet, &format!("c\n{n}\n")).reasons == 1)
+        .collect();
+    assert_eq!(right.len(), 1);
+}
+
+#[test]
+fn bad_answers_are_asked_again() {
+    let snippet = snippets::find("8", "index-past-the-end").unwrap();
+    let score = play(&[snippet], "maybe\np\n0\n9\n1\n");
+    assert_eq!(score.asked, 1);
+    assert_eq!(score.outcomes, 1);
+}
+
+#[test]
+fn leaving_early_keeps_the_score_so_far() {
+    let questions = snippets::from_chapter(None);
+    let score = play(&questions, "c\n1\np\nexit\n");
+    assert_eq!(score.asked, 2);
+}
+
+#[test]
+fn every_snippet_belongs_to_a_chapter() {
+    for snippet in SNIPPETS {
+        assert!(
+            chapter::find(snippet.chapter).is_some(),
+            "{} is in chapter {}",
+            snippet.name,
+            snippet.chapter
+        );
+        let same_name = SNIPPETS
+            .iter()
+            .filter(|other| other.chapter == snippet.chapter && other.name == snippet.name)
+            .count();
+        assert_eq!(same_name, 1, "{} is used twice", snippet.name);
+    }
+}
@@ -0,0 +1,55 @@
+// scripted sessions against the repl, input comes from a string instead of stdin
+use std::io::Cursor;
+
+use rust_book::output::{Capture, Event};
+use rust_book::repl::{Repl, PROGRAMS, PROMPT};
+
+fn session(script: &str) -> (Repl, Vec<String>) {
+    let mut repl = Repl::new();
+    let mut capture = Capture::new();
+    repl.run(&mut Cursor::new(script), &mut capture);
+    let lines = capture
+        .events
+        .into_iter()
+        .filter_map(|event| match event {
+            Event::Line(line) if line != PROMPT => Some(line),
+            _ => None,
+        })
+        .collect();
+    (repl, lines)
+}
+
+#[test]
+fn help_lists_every_program() {
+    let (_, lines) = session("help\nexit\n");
+    for program in PROGRAMS {
+        assert!(
+            lines.iter().any(|line| line.contains(program.name)),
+            "{} missing from help",
+            program.name
+        );
+    }
+}
+
+#[test]
+fn programs_can_be_entered_and_left() {
+    let (_, lines) = session("pig-latin\nfirst apple\nexit\ntemperature\n212\nexit\nexit\n");
+    assert!(lines.contains(&String::from("irst-fay apple-hay")));
+    assert!(lines.contains(&String::from("212℉ = 100.00℃")));
+    assert_eq!(lines.last().unwrap(), "Bye!");
+}
+
+#[test]
+fn history_can_be_replayed() {
+    let (repl, lines) = session("pig-latin\nhello\nexit\n!1\nworld\nexit\n!5\n");
+    assert_eq!(repl.history(), ["pig-latin", "pig-latin"]);
+    assert!(lines.contains(&String::from("ello-hay")));
+    assert!(lines.contains(&String::from("orld-way")));
+    assert!(lines.contains(&String::from("no command !5 in the history")));
+}
+
+#[test]
+fn end_of_input_leaves_the_shell() {
+    let (_, lines) = session("employees\nadd Sally to Engineering\n");
+    assert_eq!(lines.last().unwrap(), "Bye!");
+}
@@ -0,0 +1,153 @@
+// the scores file round trip and the leaderboard ranking
+use std::{fs, io::Cursor, time::Duration};
+
+use rust_book::guessing_game::{self, Difficulty, End, Feedback, Settings, Summary};
+use rust_book::output::{Capture, Event};
+use rust_book::scores::{self, Score};
+
+mod common;
+use common::TempDir;
+
+fn score(player: &str, difficulty: Difficulty, won: bool, attempts: u32, millis: u64) -> Score {
+    let settings = difficulty.settings();
+    Score {
+        player: player.to_string(),
+        range: settings.range,
+        max_attempts: settings.max_attempts,
+        feedback: settings.feedback,
+        won,
+        attempts,
+        duration: Duration::from_millis(millis),
+        timestamp: 1_700_000_000,
+    }
+}
+
+#[test]
+fn scores_survive_a_round_trip() {
+    let dir = TempDir::new("scores");
+    let path = dir.file("round_trip.tsv");
+    let written = [
+        score("ferris", Difficulty::Normal, true, 6, 12_345),
+        score("corro", Difficulty::Easy, false, 3, 800),
+    ];
+    for score in &written {
+        scores::record(&path, score).unwrap();
+    }
+    let file = scores::load(&path).unwrap();
+    assert_eq!(file.scores, written);
+    assert_eq!(file.skipped, 0);
+}
+
+#[test]
+fn damaged_and_missing_files_are_handled() {
+    let dir = TempDir::new("scores");
+    let path = dir.file("damaged.tsv");
+    assert!(scores::load(&path).unwrap().scores.is_empty());
+
+    scores::record(&path, &score("ferris", Difficulty::Hard, true, 9, 1_000)).unwrap();
+    let mut text = fs::read(&path).unwrap();
+    text.extend_from_slice(b"ferris\t1\tone hundred\n\xff\xfe\x00\n\n");
+    fs::write(&path, text).unwrap();
+
+    let file = scores::load(&path).unwrap();
+    assert_eq!(file.scores.len(), 1);
+    assert_eq!(file.skipped, 2);
+
+    let mut capture = Capture::new();
+    scores::print(&file, None, &mut capture);
+    assert!(capture.events.contains(&Event::Line(String::from(
+        "(2 damaged lines in the scores file were skipped)"
+    ))));
+}
+
+#[test]
+fn older_lines_are_classic_games() {
+    let dir = TempDir::new("scores");
+    let path = dir.file("older.tsv");
+    fs::write(&path, "ferris\t1\t100\t10\twon\t6\t4200\t1700000000\n").unwrap();
+    let file = scores::load(&path).unwrap();
+    assert_eq!(
+        file.scores,
+        [score("ferris", Difficulty::Normal, true, 6, 4_200)]
+    );
+
+    let mut liar = file.scores[0].clone();
+    liar.feedback = Feedback::Liar(0.25);
+    scores::record(&path, &liar).unwrap();
+    assert_eq!(scores::load(&path).unwrap().scores[1], liar);
+    assert_eq!(liar.difficulty(), "normal, liar:0.25");
+}
+
+#[test]
+fn players_are_ranked_per_difficulty() {
+    let boards = scores::leaderboard(&[
+        score("slow", Difficulty::Normal, true, 5, 60_000),
+        score("fast", Difficulty::Normal, true, 5, 9_000),
+        score("lucky", Difficulty::Normal, true, 2, 1_000),
+        score("unlucky", Difficulty::Normal, false, 10, 1_000),
+        score("slow", Difficulty::Hard, true, 8, 1_000),
+        score("custom", Difficulty::Easy, true, 1, 1_000),
+    ]);
+    let players: Vec<&str> = boards["normal"]
+        .iter()
+        .map(|standing| standing.player.as_str())
+        .collect();
+    assert_eq!(players, ["lucky", "fast", "slow", "unlucky"]);
+    assert_eq!(boards["hard"].len(), 1);
+    assert_eq!(boards.len(), 3);
+}
+
+#[test]
+fn only_finished_games_are_scored() {
+    let settings = Settings::new(1..=500, Some(5)).unwrap();
+    let summary = |end| Summary {
+        secret: 250,
+        guesses: vec![250],
+        hints: 0,
+        end,
+        duration: Duration::from_secs(3),
+    };
+    assert!(Score::new(&settings, &summary(End::GaveUp), "ferris").is_none());
+    let won = Score::new(&settings, &summary(End::Won), "fer\tris").unwrap();
+    assert_eq!(won.player, "fer ris");
+    assert_eq!(
+        won.difficulty(),
+        "custom (a number from 1 to 500, 5 guesses)"
+    );
+}
+
+#[test]
+fn the_repl_game_saves_to_the_given_file() {
+    let dir = TempDir::new("scores");
+    let path = dir.file("repl.tsv");
+    // every number in the easy range, one of them is the secret
+    let script: String = ["easy"]
+        .into_iter()
+        .map(String::from)
+        .chain((1..=10).map(|n| n.to_string()))
+        .map(|line| line + "\n")
+        .collect();
+    let mut capture = Capture::new();
+    guessing_game::play_and_save(
+        &mut Cursor::new(script),
+        &mut capture,
+        Some(&path),
+        "ferris",
+    );
+    let file = scores::load(&path).unwrap();
+    assert_eq!(file.scores.len(), 1);
+    let score = &file.scores[0];
+    assert_eq!(score.player, "ferris");
+    assert_eq!(score.range, 1..=10);
+    assert!(score.won);
+
+    // giving up isn't a score, and without a file nothing is written
+    guessing_game::play_and_save(
+        &mut Cursor::new("easy\n"),
+        &mut capture,
+        Some(&path),
+        "ferris",
+    );
+    guessing_game::play_and_save(&mut Cursor::new("easy\n1\n"), &mut capture, None, "ferris");
+    assert_eq!(scores::load(&path).unwrap().scores.len(), 1);
+}
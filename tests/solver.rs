@@ -0,0 +1,63 @@
+// the strategies are compared over seeded games, so the numbers repeat from run to run
+use rand::{rngs::StdRng, SeedableRng};
+
+use rust_book::solver::{self, Binary, LearnedMedian, Linear, Strategy};
+
+#[test]
+fn binary_search_stays_within_the_bound() {
+    for range in [1..=1, 1..=100, 1..=1000, -500..=500] {
+        let stats = solver::compare(&mut Binary, range.clone(), 2_000, 4);
+        assert!(
+            stats.worst() <= solver::binary_bound(&range),
+            "{range:?} took {} guesses",
+            stats.worst()
+        );
+    }
+    assert_eq!(solver::binary_bound(&(1..=100)), 7);
+    assert_eq!(solver::binary_bound(&(1..=1024)), 11);
+}
+
+#[test]
+fn binary_search_handles_the_whole_i32_range() {
+    let mut rng = StdRng::seed_from_u64(0);
+    assert_eq!(Binary.guess(i32::MIN, i32::MAX, &mut rng), -1);
+    assert_eq!(Binary.guess(i32::MAX - 1, i32::MAX, &mut rng), i32::MAX - 1);
+    assert_eq!(Binary.guess(i32::MIN, i32::MIN + 1, &mut rng), i32::MIN);
+    let stats = solver::compare(&mut Binary, i32::MIN..=i32::MAX, 200, 4);
+    assert!(stats.worst() <= 32);
+}
+
+#[test]
+fn the_same_seed_plays_the_same_games() {
+    for name in ["binary", "random", "linear", "learned-median"] {
+        let play = |seed| {
+            let mut strategy = solver::strategy(name).unwrap();
+            solver::compare(strategy.as_mut(), 1..=100, 500, seed)
+        };
+        assert_eq!(play(9), play(9), "{name}");
+    }
+}
+
+#[test]
+fn stats_add_up() {
+    let stats = solver::compare(&mut Linear, 1..=10, 1_000, 2);
+    assert_eq!(stats.distribution().values().sum::<usize>(), 1_000);
+    assert!(stats.worst() <= 10 && stats.best() >= 1);
+    assert!((stats.mean() - 5.5).abs() < 0.5);
+    // linear search is much slower than halving the range every time
+    let binary = solver::compare(&mut Binary, 1..=10, 1_000, 2);
+    assert!(binary.mean() < stats.mean());
+}
+
+#[test]
+fn learned_median_guesses_where_secrets_have_been() {
+    let mut rng = StdRng::seed_from_u64(0);
+    let mut strategy = LearnedMedian::default();
+    assert_eq!(strategy.guess(1, 100, &mut rng), 50);
+    for secret in [30, 31, 29] {
+        strategy.learn(secret);
+    }
+    assert_eq!(strategy.guess(1, 100, &mut rng), 30);
+    // none of the earlier secrets are possible any more, back to the middle
+    assert_eq!(strategy.guess(60, 100, &mut rng), 80);
+}
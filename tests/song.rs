@@ -0,0 +1,123 @@
+use rust_book::song::{self, Song, SongError, Style, SONGS};
+
+#[test]
+fn the_built_in_songs_are_all_there() {
+    for (name, _) in SONGS {
+        assert!(song::builtin(name).is_some(), "{name}");
+    }
+    let twelve_days = song::builtin("Twelve-Days").unwrap();
+    assert_eq!(twelve_days.len(), 12);
+    let last = twelve_days.verse(12);
+    assert_eq!(last.len(), 13);
+    assert_eq!(
+        last[..3],
+        [
+            "On the twelfth day of Christmas My true love sent to me.",
+            "12 drummers drumming",
+            "11 pipers piping"
+        ]
+    );
+    assert_eq!(last[12], "A partridge in a pear tree");
+    assert!(song::builtin("greensleeves").is_none());
+}
+
+#[test]
+fn empty_lines_are_left_out() {
+    let house = song::builtin("house-that-jack-built").unwrap();
+    assert_eq!(house.verse(1), ["This is the house that Jack built"]);
+    assert_eq!(
+        house.verse(3),
+        [
+            "This is the rat",
+            "That ate the malt",
+            "That lay in the house that Jack built"
+        ]
+    );
+    let farm = song::builtin("old-macdonald").unwrap();
+    assert_eq!(farm.verse(2).len(), 2 + 2 * 2 + 1);
+    assert_eq!(
+        farm.verse(2)[2],
+        "With an oink oink here and an oink oink there"
+    );
+}
+
+#[test]
+fn ordinals_past_twelve_get_the_right_ending() {
+    let items: String = (1..=113).map(|n| format!("item: {n}\n")).collect();
+    let song: Song =
+        format!("title: Counting\nfields: x\nverse: the {{nth}} verse\nverse: {{ordinal}} time, {{words}} in all\neach: {{x}}\n{items}")
+            .parse()
+            .unwrap();
+    for (n, nth) in [
+        (1, "1st"),
+        (2, "2nd"),
+        (3, "3rd"),
+        (11, "11th"),
+        (12, "12th"),
+        (13, "13th"),
+        (21, "21st"),
+        (22, "22nd"),
+        (101, "101st"),
+        (111, "111th"),
+        (112, "112th"),
+    ] {
+        assert_eq!(song.verse(n)[0], format!("the {nth} verse"));
+    }
+    assert_eq!(song.verse(21)[1], "twenty-first time, twenty-one in all");
+    assert_eq!(
+        song.verse(113)[1],
+        "one hundred and thirteenth time, one hundred and thirteen in all"
+    );
+    {}
+}
+
+#[test]
+fn mistakes_in_a_song_file_say_which_line() {
+    let parse = |text: &str| text.parse::<Song>().unwrap_err();
+    assert_eq!(
+        parse("title: t\nfields: a\n\neach: {b}\nitem: x"),
+        SongError::UnknownField {
+            line: 4,
+            name: String::from("b")
+        }
+    );
+    assert_eq!(
+        parse("title: t\nfields: a | b\neach: {a}\nitem: x"),
+        SongError::WrongFields {
+            line: 4,
+            expected: 2,
+            found: 1
+        }
+    );
+    assert!(matches!(
+        parse("# a song\nname: t"),
+        SongError::Syntax { line: 2, .. }
+    ));
+    assert_eq!(
+        parse("title: t\nfields: a\neach: {a}"),
+        SongError::Missing("items")
+    );
+    assert_eq!(
+        parse("fields: a\neach: {a}\nitem: x"),
+        SongError::Missing("title")
+    );
+}
+
+#[test]
+fn markdown_and_html_keep_the_line_breaks_and_escape_the_words() {
+    let song: Song = "title: Fish & <Chips>\nfields: food\nverse: *{food}*\neach: {food}\nitem: cod\nitem: chips"
+        .parse()
+        .unwrap();
+    assert_eq!(
+        song.render(Style::Plain),
+        "Fish & <Chips>\n\n*cod*\ncod\n\n*chips*\nchips\ncod\n"
+    );
+    assert_eq!(
+        song.render(Style::Markdown),
+        "# Fish & \\<Chips\\>\n\n\\*cod\\*\\\ncod\n\n\\*chips\\*\\\nchips\\\ncod\n"
+    );
+    assert_eq!(
+        song.render(Style::Html),
+        "<h1>Fish &amp; &lt;Chips&gt;</h1>\n<p>\n*cod*<br>\ncod\n</p>\n<p>\n*chips*<br>\nchips<br>\ncod\n</p>\n"
+    );
+}
@@ -0,0 +1,72 @@
+use rust_book::f_to_c;
+use rust_book::table::{self, Steps, Style, Table};
+use rust_book::units::{self, UnitError};
+
+fn fahrenheit_to_celsius(start: f64, stop: f64, step: f64) -> Table {
+    table::conversions(
+        units::find("F").unwrap(),
+        &[units::find("C").unwrap()],
+        &Steps::new(start, stop, step).unwrap(),
+    )
+    .unwrap()
+}
+
+#[test]
+fn rows_agree_with_f_to_c() {
+    let table = fahrenheit_to_celsius(-40.0, 212.0, 4.0);
+    assert_eq!(table.rows.len(), 64);
+    for row in &table.rows {
+        assert!((row[1] - f64::from(f_to_c(row[0] as f32))).abs() < 1e-4);
+    }
+}
+
+#[test]
+fn steps_include_the_stop_and_can_count_down() {
+    assert_eq!(
+        Steps::new(0.0, 1.0, 0.1).unwrap().values().len(),
+        11,
+        "adding up 0.1s falls just short of 1"
+    );
+    assert_eq!(
+        Steps::new(10.0, 0.0, -4.0).unwrap().values(),
+        [10.0, 6.0, 2.0]
+    );
+    assert_eq!(Steps::new(5.0, 5.0, 0.0).unwrap().values(), [5.0]);
+    assert!(Steps::new(0.0, 10.0, 0.0).is_err());
+    assert!(Steps::new(0.0, 10.0, -1.0).is_err());
+    assert!(Steps::new(0.0, 1e9, 1.0).is_err());
+}
+
+#[test]
+fn tables_render_as_plain_text_csv_and_markdown() {
+    let table = fahrenheit_to_celsius(32.0, 212.0, 90.0);
+    assert_eq!(
+        table.render(Style::Plain, 1),
+        "    ℉      ℃\n-----  -----\n 32.0    0.0\n122.0   50.0\n212.0  100.0\n"
+    );
+    assert_eq!(table.render(Style::Csv, 0), "℉,℃\n32,0\n122,50\n212,100\n");
+    assert_eq!(
+        table.render(Style::Markdown, 0),
+        "|   ℉ |   ℃ |\n|----:|----:|\n|  32 |   0 |\n| 122 |  50 |\n| 212 | 100 |\n"
+    );
+}
+
+#[test]
+fn rounding_never_shows_minus_zero() {
+    // 31.999℉ is -0.00056℃
+    let table = fahrenheit_to_celsius(31.999, 31.999, 0.0);
+    assert_eq!(table.render(Style::Csv, 2), "℉,℃\n32.00,0.00\n");
+}
+
+#[test]
+fn tables_stop_at_absolute_zero() {
+    let steps = Steps::new(-300.0, 0.0, 100.0).unwrap();
+    assert!(matches!(
+        table::conversions(
+            units::find("C").unwrap(),
+            &[units::find("K").unwrap()],
+            &steps
+        ),
+        Err(UnitError::BelowAbsoluteZero(_))
+    ));
+}
@@ -0,0 +1,111 @@
+use rust_book::temperature::{Celsius, Fahrenheit, Kelvin, Rankine, Temperature, TemperatureError};
+
+fn close(a: f64, b: f64) -> bool {
+    (a - b).abs() < 1e-9
+}
+
+#[test]
+fn fixed_points_agree_on_every_scale() {
+    // absolute zero, water freezing, -40 where ℃ and ℉ meet, water boiling
+    let points = [
+        (-273.15, -459.67, 0.0, 0.0),
+        (0.0, 32.0, 273.15, 491.67),
+        (-40.0, -40.0, 233.15, 419.67),
+        (100.0, 212.0, 373.15, 671.67),
+    ];
+    for (c, f, k, r) in points {
+        let celsius = Celsius::new(c).unwrap();
+        assert!(close(Fahrenheit::from(celsius).degrees(), f), "{c}℃ in ℉");
+        assert!(close(Kelvin::from(celsius).degrees(), k), "{c}℃ in K");
+        assert!(close(Rankine::from(celsius).degrees(), r), "{c}℃ in °R");
+        let fahrenheit = Fahrenheit::new(f).unwrap();
+        assert!(close(Celsius::from(fahrenheit).degrees(), c), "{f}℉ in ℃");
+        assert!(close(Kelvin::from(Rankine::new(r).unwrap()).degrees(), k));
+    }
+}
+
+#[test]
+fn conversions_round_trip() {
+    for degrees in [-459.67, -40.0, 0.0, 37.5, 98.6, 451.0, 1e6] {
+        let f = Fahrenheit::new(degrees).unwrap();
+        let back = Fahrenheit::from(Kelvin::from(Celsius::from(Rankine::from(f))));
+        assert!(
+            close(back.degrees(), degrees),
+            "{degrees}℉ came back as {back}"
+        );
+    }
+}
+
+#[test]
+fn temperatures_parse_with_any_unit() {
+    assert_eq!(
+        "112F".parse::<Temperature>(),
+        Ok(Temperature::Fahrenheit(Fahrenheit::new(112.0).unwrap()))
+    );
+    assert_eq!(
+        "-40 °C".parse::<Temperature>(),
+        Ok(Temperature::Celsius(Celsius::new(-40.0).unwrap()))
+    );
+    for (text, kelvin) in [
+        ("300K", 300.0),
+        ("300 kelvin", 300.0),
+        ("26.85℃", 300.0),
+        ("80.33 ℉", 300.0),
+        ("540°R", 300.0),
+        ("1e2 c", 373.15),
+    ] {
+        let parsed: Temperature = text.parse().unwrap();
+        assert!(close(Kelvin::from(parsed).degrees(), kelvin), "{text}");
+    }
+    // a scale's own parse converts anything with a unit and takes a plain number as its own
+    assert!(close(
+        "100C".parse::<Fahrenheit>().unwrap().degrees(),
+        212.0
+    ));
+    assert_eq!("20".parse::<Celsius>(), Ok(Celsius::new(20.0).unwrap()));
+    assert_eq!(
+        "212".parse::<Fahrenheit>(),
+        Ok(Fahrenheit::new(212.0).unwrap())
+    );
+}
+
+#[test]
+fn bad_temperatures_are_rejected() {
+    assert!(matches!(
+        "-300C".parse::<Temperature>(),
+        Err(TemperatureError::BelowAbsoluteZero(_))
+    ));
+    assert!(matches!(
+        Kelvin::new(-0.01),
+        Err(TemperatureError::BelowAbsoluteZero(_))
+    ));
+    assert!(Kelvin::new(f64::NAN).is_err());
+    assert_eq!(
+        "20".parse::<Temperature>(),
+        Err(TemperatureError::MissingUnit(String::from("20")))
+    );
+    assert_eq!(
+        "20 X".parse::<Temperature>(),
+        Err(TemperatureError::UnknownUnit(String::from("X")))
+    );
+    assert_eq!(
+        "warm".parse::<Celsius>(),
+        Err(TemperatureError::NotANumber(String::new()))
+    );
+    assert_eq!(
+        "-500F".parse::<Fahrenheit>().unwrap_err().to_string(),
+        "-500℉ is below absolute zero (-459.67℉)"
+    );
+}
+
+#[test]
+fn display_puts_the_symbol_after_the_rounded_number() {
+    let c = Celsius::from(Fahrenheit::new(112.0).unwrap());
+    assert_eq!(format!("{c:.2}"), "44.44℃");
+    assert_eq!(Kelvin::new(300.0).unwrap().to_string(), "300K");
+    assert_eq!(format!("{:.1}", Rankine::new(491.67).unwrap()), "491.7°R");
+    assert_eq!(
+        Temperature::from(Fahrenheit::new(-40.0).unwrap()).to_string(),
+        "-40℉"
+    );
+}
@@ -0,0 +1,155 @@
+use std::io::Cursor;
+
+use rust_book::output::{Capture, Event};
+use rust_book::table::{self, Steps, Style};
+use rust_book::temperature::Scale;
+use rust_book::units::{self, Conversion, Dimension, Measure, Quantity, UnitError, UNITS};
+
+fn convert(expression: &str) -> Result<Quantity, UnitError> {
+    Conversion::parse(expression)?.result()
+}
+
+fn close(a: f64, b: f64) -> bool {
+    (a - b).abs() < 1e-9 * b.abs().max(1.0)
+}
+
+#[test]
+fn compound_quantities_are_added_up() {
+    let height = convert("5 ft 3 in to cm").unwrap();
+    assert!(close(height.value, 160.02));
+    assert_eq!(height.unit.name, "centimetre");
+    assert_eq!(format!("{height:.1}"), "160.0 cm");
+    // no spaces needed between numbers and units
+    assert!(close(convert("5ft3in to cm").unwrap().value, 160.02));
+    assert_eq!(convert("3h to s").unwrap().to_string(), "10800 s");
+    assert_eq!(convert("1 week 2 days to hours").unwrap().value, 216.0);
+    assert!(close(convert("2.5e3 m to km").unwrap().value, 2.5));
+}
+
+#[test]
+fn temperatures_convert_through_absolute_zero() {
+    assert!(close(convert("100 C to F").unwrap().value, 212.0));
+    assert!(close(convert("-40 °F to °C").unwrap().value, -40.0));
+    assert!(close(convert("0 K to rankine").unwrap().value, 0.0));
+    assert!(matches!(
+        convert("-300 C to K"),
+        Err(UnitError::BelowAbsoluteZero(_))
+    ));
+    assert_eq!(convert("5 C 3 F to K"), Err(UnitError::AddedTemperatures));
+}
+
+#[test]
+fn units_are_found_by_symbol_name_or_plural() {
+    assert_eq!(units::find("FT").unwrap().name, "foot");
+    assert_eq!(units::find("pounds").unwrap().name, "pound");
+    assert_eq!(units::find("ms").unwrap().name, "millisecond");
+    assert_eq!(units::find("cups").unwrap().name, "cup");
+    assert_eq!(units::find("℃").unwrap().name, "celsius");
+    assert_eq!(units::find("meters").unwrap().name, "metre");
+    assert!(units::find("parsec").is_none());
+    // only names are plural, cs isn't lots of ℃
+    assert!(units::find("cs").is_none());
+    assert_eq!(
+        convert("3 cs to s"),
+        Err(UnitError::UnknownUnit(String::from("cs")))
+    );
+    // symbols aren't shared between units
+    for unit in UNITS {
+        for symbol in unit
+            .symbols
+            .iter()
+            .chain(unit.spellings)
+            .chain([&unit.name])
+        {
+            assert_eq!(units::find(symbol), Some(unit), "{symbol}");
+        }
+    }
+}
+
+#[test]
+fn every_dimension_has_a_base_unit() {
+    for dimension in Dimension::ALL {
+        assert!(
+            units::of(dimension).any(|unit| {
+                unit.measure == Measure::Factor(1.0)
+                    || unit.measure == Measure::Scale(Scale::Kelvin)
+            }),
+            "{dimension}"
+        );
+    }
+}
+
+#[test]
+fn mistakes_are_errors_not_panics() {
+    let incompatible = convert("3 kg to s").unwrap_err();
+    assert!(matches!(
+        incompatible,
+        UnitError::Incompatible { from, to } if from.name == "kilogram" && to.name == "second"
+    ));
+    assert_eq!(
+        incompatible.to_string(),
+        "can't convert kg (mass) to s (time)"
+    );
+    assert!(matches!(
+        convert("5 ft 2 kg to m"),
+        Err(UnitError::Incompatible { .. })
+    ));
+    assert_eq!(
+        convert("5 parsecs to m"),
+        Err(UnitError::UnknownUnit(String::from("parsecs")))
+    );
+    for bad in ["", "5 ft", "to m", "5 ft 3 to cm", "ft 5 to m"] {
+        assert!(convert(bad).is_err(), "{bad}");
+    }
+}
+
+#[test]
+fn only_finite_numbers_are_converted() {
+    for (expression, number) in [
+        ("nan ft to m", "nan"),
+        ("inf C to K", "inf"),
+        ("-infinity s to h", "-infinity"),
+        ("1e999 m to km", "1e999"),
+    ] {
+        assert_eq!(
+            Conversion::parse(expression),
+            Err(UnitError::NotANumber(String::from(number))),
+            "{expression}"
+        );
+    }
+}
+
+#[test]
+fn tables_work_with_any_units() {
+    let steps = Steps::new(1.0, 3.0, 1.0).unwrap();
+    let feet = units::find("ft").unwrap();
+    let metres = units::find("m").unwrap();
+    let table = table::conversions(feet, &[metres], &steps).unwrap();
+    assert_eq!(
+        table.render(Style::Csv, 4),
+        "ft,m\n1.0000,0.3048\n2.0000,0.6096\n3.0000,0.9144\n"
+    );
+    let seconds = units::find("s").unwrap();
+    assert!(table::conversions(feet, &[seconds], &steps).is_err());
+}
+
+#[test]
+fn the_converter_keeps_going_after_a_mistake() {
+    let mut capture = Capture::new();
+    units::converter(
+        &mut Cursor::new("3 kg to s\n1 mi to km\nexit\n"),
+        &mut capture,
+    );
+    let lines: Vec<&str> = capture
+        .events
+        .iter()
+        .filter_map(|event| match event {
+            Event::Line(line) if line != "convert> " => Some(line.as_str()),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(
+        lines[1..],
+        ["can't convert kg (mass) to s (time)", "1 mi = 1.609344 km"]
+    );
+}
@@ -0,0 +1,64 @@
+// compiles every snippet in the catalog, so this needs rustc on the PATH (or $RUSTC)
+use rust_book::output::Capture;
+use rust_book::snippets::{self, Outcome};
+use rust_book::verify::{self, Found};
+
+#[test]
+fn error_codes_are_read_from_rustc_output() {
+    let stderr =
+        "error[E0499]: cannot borrow `s` as mutable more than once at a time\n --> a.rs:4:14\n";
+    assert_eq!(verify::error_code(stderr).as_deref(), Some("E0499"));
+    assert_eq!(
+        verify::error_code("error: expected expression, found `let` statement\n"),
+        None
+    );
+}
+
+#[test]
+fn error_codes_have_to_match() {
+    let found = Found::CompileError(Some(String::from("E0502")));
+    assert!(found.matches(Outcome::CompileError(Some("E0502"))));
+    assert!(!found.matches(Outcome::CompileError(Some("E0499"))));
+    assert!(!found.matches(Outcome::CompileError(None)));
+    assert!(!Found::Panic.matches(Outcome::Compiles));
+}
+
+#[test]
+fn every_snippet_does_what_the_notes_say() {
+    let checks = verify::check_all(&snippets::from_chapter(None)).unwrap();
+    let mut capture = Capture::new();
+    let passed = verify::report(&checks, &mut capture);
+    assert!(
+        passed,
+        "{}",
+        capture.render(rust_book::output::Format::Plain)
+    );
+}
+
+// the notes in the chapters point at snippets as "(quiz snippet 4 dangling-reference)", so
+// every one of them has to be in the catalog or verify would never check what it says
+#[test]
+fn every_note_points_at_a_snippet() {
+    let src = concat!(env!("CARGO_MANIFEST_DIR"), "/src");
+    let mut notes = 0;
+    for entry in std::fs::read_dir(src).unwrap() {
+        let path = entry.unwrap().path();
+        let text = std::fs::read_to_string(&path).unwrap();
+        for (line, note) in text.lines().enumerate().filter_map(|(i, line)| {
+            let start = line.find("(quiz snippet ")? + "(quiz snippet ".len();
+            let end = line[start..].find(')')?;
+            Some((i + 1, &line[start..start + end]))
+        }) {
+            let (chapter, name) = note.split_once(' ').unwrap();
+            assert!(
+                snippets::SNIPPETS
+                    .iter()
+                    .any(|snippet| snippet.chapter == chapter && snippet.name == name),
+                "{}:{line} points at quiz snippet {chapter} {name} which isn't in the catalog",
+                path.display()
+            );
+            notes += 1;
+        }
+    }
+    assert!(notes > 0);
+}